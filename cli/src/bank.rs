@@ -0,0 +1,46 @@
+//! The cluster state that commands read and submit transactions to.
+
+use {
+    crate::error::CliError, solana_account::Account, solana_hash::Hash, solana_pubkey::Pubkey,
+    solana_rpc_client::rpc_client::RpcClient, solana_signature::Signature,
+    solana_transaction::Transaction,
+};
+
+/// A source of accounts and a sink for transactions, such as an RPC node.
+///
+/// Commands only go through this trait, so they can run against an
+/// in-process bank in tests.
+pub trait Bank {
+    /// Returns the account at `pubkey`, if it exists.
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, CliError>;
+
+    /// Returns the minimum balance for an account of `space` bytes to be
+    /// rent exempt.
+    fn minimum_balance(&self, space: usize) -> Result<u64, CliError>;
+
+    /// Returns a recent blockhash for new transactions.
+    fn latest_blockhash(&self) -> Result<Hash, CliError>;
+
+    /// Submits a signed transaction and waits for it to be confirmed.
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, CliError>;
+}
+
+impl Bank for RpcClient {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, CliError> {
+        Ok(self
+            .get_account_with_commitment(pubkey, self.commitment())?
+            .value)
+    }
+
+    fn minimum_balance(&self, space: usize) -> Result<u64, CliError> {
+        Ok(self.get_minimum_balance_for_rent_exemption(space)?)
+    }
+
+    fn latest_blockhash(&self) -> Result<Hash, CliError> {
+        Ok(self.get_latest_blockhash()?)
+    }
+
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, CliError> {
+        Ok(self.send_and_confirm_transaction(transaction)?)
+    }
+}
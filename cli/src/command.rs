@@ -0,0 +1,528 @@
+//! The CLI commands, run against a [`Bank`].
+//!
+//! Commands that change a config account build their instructions with
+//! `solana_config_interface::instruction`, check `store` instructions with
+//! `preflight_store` and `set_keys` instructions with the same checks the
+//! program applies, and then either sign and send the transaction or return
+//! it unsigned for offline signing.
+
+use {
+    crate::{
+        bank::Bank,
+        error::CliError,
+        offline::fetch_nonce,
+        payload::{format_key, PayloadType, RawPayload},
+    },
+    base64::{prelude::BASE64_STANDARD, Engine},
+    solana_account::Account,
+    solana_config_interface::{
+        id,
+        instruction::{set_keys, store as store_instruction, store_with_signers, MAX_STORE_KEYS},
+        space::{ConfigSpace, SpaceError},
+        state::{ConfigKeys, ConfigKeysRef},
+        validate::{
+            check_stored_signers, check_threshold, check_unique_keys, preflight_store, StoreError,
+        },
+    },
+    solana_hash::Hash,
+    solana_instruction::Instruction,
+    solana_keypair::Keypair,
+    solana_message::Message,
+    solana_pubkey::Pubkey,
+    solana_sanitize::Sanitize,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::{advance_nonce_account, create_account},
+    solana_transaction::Transaction,
+    std::fmt::Write,
+};
+
+/// How to finish a transaction.
+#[derive(Default)]
+pub struct TransactionOptions {
+    /// The keypairs to sign with.
+    pub keypairs: Vec<Keypair>,
+    /// The fee payer, by default the first keypair.
+    pub fee_payer: Option<Pubkey>,
+    /// The recent blockhash, by default the bank's latest, or the durable
+    /// nonce stored in `nonce`.
+    pub blockhash: Option<Hash>,
+    /// A nonce account, to make the transaction durable: it stays valid
+    /// until the nonce advances, rather than until its blockhash expires.
+    pub nonce: Option<Pubkey>,
+    /// The nonce authority, by default the fee payer.
+    pub nonce_authority: Option<Pubkey>,
+    /// Return the transaction unsigned, rather than signing and sending it.
+    pub unsigned: bool,
+}
+
+/// The result of a command that builds a transaction.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The transaction was signed, sent and confirmed.
+    Sent(Signature),
+    /// The unsigned transaction, for signing offline.
+    Unsigned(Transaction),
+}
+
+/// Encode a transaction as base64 bincode, as RPC nodes accept it.
+pub fn encode_transaction(transaction: &Transaction) -> String {
+    BASE64_STANDARD.encode(bincode::serialize(transaction).unwrap())
+}
+
+/// Decode a transaction encoded by [`encode_transaction`], checking that its
+/// account indexes are in bounds and it has a signature for each required
+/// signer.
+pub fn decode_transaction(encoded: &str) -> Result<Transaction, CliError> {
+    let bytes = BASE64_STANDARD
+        .decode(encoded.trim())
+        .map_err(|err| CliError::invalid("transaction", err))?;
+    let transaction = bincode::deserialize::<Transaction>(&bytes)
+        .map_err(|err| CliError::invalid("transaction", err))?;
+    transaction
+        .sanitize()
+        .map_err(|err| CliError::invalid("transaction", err))?;
+    if transaction.signatures.len()
+        != usize::from(transaction.message.header.num_required_signatures)
+    {
+        return Err(CliError::invalid(
+            "transaction",
+            "expected one signature for each required signer",
+        ));
+    }
+    Ok(transaction)
+}
+
+/// Sign `transaction` with `keypairs`, which must include every required
+/// signer.
+pub fn sign_transaction<S: Signer>(
+    transaction: &mut Transaction,
+    keypairs: &[S],
+) -> Result<(), CliError> {
+    let message_data = transaction.message_data();
+    let num_signers = usize::from(transaction.message.header.num_required_signatures);
+    for (pubkey, signature) in transaction.message.account_keys[..num_signers]
+        .iter()
+        .zip(transaction.signatures.iter_mut())
+    {
+        let keypair = keypairs
+            .iter()
+            .find(|keypair| keypair.pubkey() == *pubkey)
+            .ok_or(CliError::MissingKeypair(*pubkey))?;
+        *signature = keypair.try_sign_message(&message_data)?;
+    }
+    Ok(())
+}
+
+fn fee_payer(options: &TransactionOptions) -> Result<Pubkey, CliError> {
+    options
+        .fee_payer
+        .or_else(|| options.keypairs.first().map(Keypair::pubkey))
+        .ok_or(CliError::MissingFeePayer)
+}
+
+fn finish(
+    bank: &impl Bank,
+    options: &TransactionOptions,
+    instructions: &[Instruction],
+) -> Result<Outcome, CliError> {
+    let fee_payer = fee_payer(options)?;
+    let mut instructions = instructions.to_vec();
+    let blockhash = match options.nonce {
+        Some(nonce) => {
+            let authority = options.nonce_authority.unwrap_or(fee_payer);
+            instructions.insert(0, advance_nonce_account(&nonce, &authority));
+            match options.blockhash {
+                Some(blockhash) => blockhash,
+                None => fetch_nonce(bank, &nonce, &authority)?,
+            }
+        }
+        None => match options.blockhash {
+            Some(blockhash) => blockhash,
+            None => bank.latest_blockhash()?,
+        },
+    };
+    let message = Message::new_with_blockhash(&instructions, Some(&fee_payer), &blockhash);
+    let mut transaction = Transaction::new_unsigned(message);
+    if options.unsigned {
+        return Ok(Outcome::Unsigned(transaction));
+    }
+    sign_transaction(&mut transaction, &options.keypairs)?;
+    bank.send_transaction(&transaction).map(Outcome::Sent)
+}
+
+// Every account that signs `instructions`.
+fn signers_of(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut signers = vec![];
+    for meta in instructions
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+    {
+        if meta.is_signer && !signers.contains(&meta.pubkey) {
+            signers.push(meta.pubkey);
+        }
+    }
+    signers
+}
+
+/// A config account and its stored keys.
+pub struct ConfigAccount {
+    /// The account.
+    pub account: Account,
+    /// The keys stored at the start of its data.
+    pub keys: ConfigKeys,
+}
+
+impl ConfigAccount {
+    /// The data following the keys, including any unused space.
+    pub fn payload(&self) -> &[u8] {
+        &self.account.data[self.keys.serialized_size()..]
+    }
+
+    fn signers(&self) -> Vec<Pubkey> {
+        self.keys
+            .keys
+            .iter()
+            .filter(|(_, is_signer)| *is_signer)
+            .map(|(pubkey, _)| *pubkey)
+            .collect()
+    }
+
+    // The stored signers that will sign: all of them, or the given subset,
+    // for accounts with a signer threshold.
+    fn signing(&self, signers: Option<Vec<Pubkey>>) -> Result<Vec<Pubkey>, CliError> {
+        let stored = self.signers();
+        let Some(signers) = signers else {
+            return Ok(stored);
+        };
+        if let Some(signer) = signers.iter().find(|signer| !stored.contains(signer)) {
+            return Err(StoreError::UnknownSigner(*signer).into());
+        }
+        Ok(signers)
+    }
+}
+
+/// Fetch a config account, checking its owner and keys.
+pub fn fetch_config(bank: &impl Bank, pubkey: &Pubkey) -> Result<ConfigAccount, CliError> {
+    let account = bank
+        .get_account(pubkey)?
+        .ok_or(CliError::AccountNotFound(*pubkey))?;
+    if account.owner != id() {
+        return Err(CliError::InvalidConfigAccount(*pubkey));
+    }
+    let keys = ConfigKeysRef::new(&account.data)
+        .map_err(|_| CliError::InvalidConfigAccount(*pubkey))?
+        .to_config_keys();
+    Ok(ConfigAccount { account, keys })
+}
+
+/// Create a config account with room for `keys` and a payload of up to
+/// `max_payload_len` bytes, and store `keys` and `payload` in it.
+///
+/// The fee payer funds the account. The config account and every signer in
+/// `keys` must sign.
+pub fn create(
+    bank: &impl Bank,
+    options: &TransactionOptions,
+    config: &Pubkey,
+    keys: Vec<(Pubkey, bool)>,
+    max_payload_len: u64,
+    payload: &[u8],
+) -> Result<Outcome, CliError> {
+    if bank.get_account(config)?.is_some() {
+        return Err(CliError::AccountExists(*config));
+    }
+    let space = ConfigSpace::for_keys(&keys, max_payload_len)?;
+    space.headroom(keys.len(), payload.len() as u64)?;
+    let lamports = bank.minimum_balance(space.space() as usize)?;
+
+    let store = store_instruction(config, true, keys, &RawPayload(payload));
+    let instructions = [
+        create_account(&fee_payer(options)?, config, lamports, space.space(), &id()),
+        store,
+    ];
+    preflight_store(
+        &vec![0; space.space() as usize],
+        &instructions[1],
+        &signers_of(&instructions),
+    )?;
+    finish(bank, options, &instructions)
+}
+
+/// Store `payload` in a config account, with new `keys`, or the stored keys
+/// if `None`.
+///
+/// Every stored signer must sign, unless the account has a signer threshold,
+/// in which case `signers` names the stored signers that will.
+pub fn store(
+    bank: &impl Bank,
+    options: &TransactionOptions,
+    config: &Pubkey,
+    keys: Option<Vec<(Pubkey, bool)>>,
+    payload: &[u8],
+    signers: Option<Vec<Pubkey>>,
+) -> Result<Outcome, CliError> {
+    let current = fetch_config(bank, config)?;
+    let keys = keys.unwrap_or_else(|| current.keys.keys.clone());
+    let is_config_signer = current.signers().is_empty()
+        || keys
+            .iter()
+            .any(|(pubkey, is_signer)| *is_signer && pubkey == config);
+
+    let instruction = if current.keys.threshold().is_some() {
+        let signing = current.signing(signers)?;
+        store_with_signers(
+            config,
+            is_config_signer,
+            keys,
+            &RawPayload(payload),
+            &signing,
+        )
+    } else {
+        store_instruction(config, is_config_signer, keys, &RawPayload(payload))
+    };
+    let instructions = [instruction];
+    preflight_store(
+        &current.account.data,
+        &instructions[0],
+        &signers_of(&instructions),
+    )?;
+    finish(bank, options, &instructions)
+}
+
+/// Replace the keys of a config account, such as to rotate its signers,
+/// keeping its payload.
+///
+/// Every stored signer must sign, unless the account has a signer threshold,
+/// in which case `signers` names the stored signers that will, and there must
+/// be at least the threshold of them.
+pub fn rotate_signers(
+    bank: &impl Bank,
+    options: &TransactionOptions,
+    config: &Pubkey,
+    keys: Vec<(Pubkey, bool)>,
+    signers: Option<Vec<Pubkey>>,
+) -> Result<Outcome, CliError> {
+    let current = fetch_config(bank, config)?;
+    if current.keys.is_frozen() {
+        return Err(StoreError::AccountFrozen.into());
+    }
+    let signing = current.signing(signers)?;
+    let instruction = set_keys(config, keys.clone(), &signing);
+    preflight_set_keys(&current, &instruction, &keys)?;
+
+    // The payload moves to follow the new keys, so longer keys push bytes
+    // off the end of the account, which must be zero.
+    let data = &current.account.data;
+    let growth = ConfigKeys { keys }
+        .serialized_size()
+        .saturating_sub(current.keys.serialized_size());
+    if data.len() < growth.saturating_add(current.keys.serialized_size())
+        || data[data.len().saturating_sub(growth)..]
+            .iter()
+            .any(|byte| *byte != 0)
+    {
+        return Err(SpaceError::InsufficientSpace.into());
+    }
+
+    finish(bank, options, &[instruction])
+}
+
+// Checks a `set_keys` instruction as the program does: the stored signers, or
+// the stored threshold of them, must sign, and the new keys must pass the
+// same checks as `store` keys.
+fn preflight_set_keys(
+    current: &ConfigAccount,
+    instruction: &Instruction,
+    keys: &[(Pubkey, bool)],
+) -> Result<(), StoreError> {
+    let (config_account, signer_accounts) = instruction
+        .accounts
+        .split_first()
+        .ok_or(StoreError::MissingConfigAccount)?;
+    check_stored_signers(
+        config_account,
+        current.signers().iter(),
+        current.keys.threshold(),
+        signer_accounts,
+    )?;
+
+    if keys.len() > MAX_STORE_KEYS {
+        return Err(StoreError::InvalidConfigKeys);
+    }
+    let new_keys_bytes = bincode::serialize(&ConfigKeys {
+        keys: keys.to_vec(),
+    })
+    .map_err(|_| StoreError::InvalidConfigKeys)?;
+    let new_keys =
+        ConfigKeysRef::new(&new_keys_bytes).map_err(|_| StoreError::InvalidConfigKeys)?;
+    check_threshold(&new_keys)?;
+    check_unique_keys(&new_keys)
+}
+
+fn write_keys(output: &mut String, keys: &ConfigKeys) {
+    for key in &keys.keys {
+        writeln!(output, "  {}", format_key(key)).unwrap();
+    }
+}
+
+/// Describe a config account: its keys, and its payload, decoded as
+/// `payload_type` or as the type its keys suggest.
+pub fn show(
+    bank: &impl Bank,
+    config: &Pubkey,
+    payload_type: Option<PayloadType>,
+) -> Result<String, CliError> {
+    let current = fetch_config(bank, config)?;
+    let keys_ref = ConfigKeysRef::new(&current.account.data)
+        .map_err(|_| CliError::InvalidConfigAccount(*config))?;
+    let mut output = String::new();
+    writeln!(output, "Config account: {config}").unwrap();
+    writeln!(output, "Balance: {} lamports", current.account.lamports).unwrap();
+    writeln!(
+        output,
+        "Space: {} bytes ({} for keys)",
+        current.account.data.len(),
+        current.keys.serialized_size()
+    )
+    .unwrap();
+    writeln!(output, "Keys:").unwrap();
+    write_keys(&mut output, &current.keys);
+    if let Some(threshold) = current.keys.threshold() {
+        writeln!(
+            output,
+            "Signer threshold: {threshold} of {}",
+            current.signers().len()
+        )
+        .unwrap();
+    }
+    if current.keys.is_frozen() {
+        writeln!(output, "Frozen").unwrap();
+    }
+    writeln!(output, "Payload hash: {}", keys_ref.data_hash()).unwrap();
+
+    let payload_type =
+        payload_type.unwrap_or_else(|| PayloadType::detect(config, &current.keys.keys));
+    match payload_type.to_json(current.payload())? {
+        Some(json) => writeln!(output, "Payload:\n{json}").unwrap(),
+        None => writeln!(output, "Payload: {}", hex::encode(current.payload())).unwrap(),
+    }
+    Ok(output)
+}
+
+/// What to compare a config account with.
+pub enum DiffTarget {
+    /// Another config account.
+    Account(Pubkey),
+    /// The account as it would be after storing `payload`, with new `keys`
+    /// or the stored keys if `None`.
+    Store {
+        /// The new keys.
+        keys: Option<Vec<(Pubkey, bool)>>,
+        /// The new payload.
+        payload: Vec<u8>,
+    },
+}
+
+// Ranges of bytes that differ between `a` and `b`, treating bytes past the
+// end of the shorter as differing.
+fn differing_ranges(a: &[u8], b: &[u8]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for i in 0..a.len().max(b.len()) {
+        if a.get(i) == b.get(i) {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some((_, end)) if *end == i => *end = i.saturating_add(1),
+            _ => ranges.push((i, i.saturating_add(1))),
+        }
+    }
+    ranges
+}
+
+fn slice(bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    bytes.get(start..end.min(bytes.len())).unwrap_or_default()
+}
+
+/// Compare a config account with `target`, listing the keys removed (`-`)
+/// and added (`+`), and the payload changes.
+pub fn diff(
+    bank: &impl Bank,
+    config: &Pubkey,
+    target: DiffTarget,
+    payload_type: Option<PayloadType>,
+) -> Result<String, CliError> {
+    let current = fetch_config(bank, config)?;
+    let (address, keys, data) = match target {
+        DiffTarget::Account(pubkey) => {
+            let other = fetch_config(bank, &pubkey)?;
+            (pubkey, other.keys, other.account.data)
+        }
+        DiffTarget::Store { keys, payload } => {
+            let keys = ConfigKeys {
+                keys: keys.unwrap_or_else(|| current.keys.keys.clone()),
+            };
+            let input = bincode::serialize(&(&keys, RawPayload(&payload))).unwrap();
+            let mut data = current.account.data.clone();
+            data.get_mut(..input.len())
+                .ok_or(StoreError::InstructionDataTooLarge)?
+                .copy_from_slice(&input);
+            (*config, keys, data)
+        }
+    };
+    let (old_payload, new_payload) = (current.payload(), &data[keys.serialized_size()..]);
+
+    let mut output = String::new();
+    for key in current
+        .keys
+        .keys
+        .iter()
+        .filter(|key| !keys.keys.contains(key))
+    {
+        writeln!(output, "- key {}", format_key(key)).unwrap();
+    }
+    for key in keys
+        .keys
+        .iter()
+        .filter(|key| !current.keys.keys.contains(key))
+    {
+        writeln!(output, "+ key {}", format_key(key)).unwrap();
+    }
+    if output.is_empty() && current.keys != keys {
+        writeln!(output, "~ keys reordered").unwrap();
+    }
+
+    let old_type = payload_type.unwrap_or_else(|| PayloadType::detect(config, &current.keys.keys));
+    let new_type = payload_type.unwrap_or_else(|| PayloadType::detect(&address, &keys.keys));
+    match (
+        old_type.to_json(old_payload).ok().flatten(),
+        new_type.to_json(new_payload).ok().flatten(),
+    ) {
+        (Some(old), Some(new)) if old_type == new_type => {
+            if old != new {
+                for line in old.lines() {
+                    writeln!(output, "- {line}").unwrap();
+                }
+                for line in new.lines() {
+                    writeln!(output, "+ {line}").unwrap();
+                }
+            }
+        }
+        _ => {
+            for (start, end) in differing_ranges(old_payload, new_payload) {
+                writeln!(
+                    output,
+                    "~ payload bytes {start}..{end}: {} -> {}",
+                    hex::encode(slice(old_payload, start, end)),
+                    hex::encode(slice(new_payload, start, end))
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    if output.is_empty() {
+        output.push_str("No differences\n");
+    }
+    Ok(output)
+}
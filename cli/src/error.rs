@@ -0,0 +1,97 @@
+//! Errors returned by the CLI.
+
+use {
+    solana_config_interface::{space::SpaceError, state::PayloadError, validate::StoreError},
+    solana_pubkey::Pubkey,
+    solana_rpc_client::api::client_error::Error as ClientError,
+    solana_signer::SignerError,
+    std::{io, path::PathBuf},
+    thiserror::Error,
+};
+
+/// Errors returned by the CLI.
+#[derive(Debug, Error)]
+pub enum CliError {
+    /// An RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    /// A file could not be read or written.
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    /// A payload, key, keypair or transaction argument could not be parsed.
+    #[error("invalid {kind}: {message}")]
+    InvalidArgument {
+        /// What the argument is, such as "payload".
+        kind: &'static str,
+        /// Why it's invalid.
+        message: String,
+    },
+    /// The account does not exist.
+    #[error("account {0} not found")]
+    AccountNotFound(Pubkey),
+    /// The account already exists.
+    #[error("account {0} already exists")]
+    AccountExists(Pubkey),
+    /// The account is not a config account.
+    #[error("account {0} is not a valid config account")]
+    InvalidConfigAccount(Pubkey),
+    /// A well-known payload is invalid.
+    #[error("invalid payload: {0}")]
+    Payload(#[from] PayloadError),
+    /// The account space is invalid.
+    #[error("invalid account space: {0}")]
+    Space(#[from] SpaceError),
+    /// The program would reject the `store`.
+    #[error("store would fail: {0}")]
+    Store(#[from] StoreError),
+    /// A signer failed to sign.
+    #[error("signing failed: {0}")]
+    Signer(#[from] SignerError),
+    /// A signer has no keypair to sign with.
+    #[error("no keypair for signer {0}")]
+    MissingKeypair(Pubkey),
+    /// Neither a fee payer nor a keypair was given.
+    #[error("no fee payer: pass --fee-payer or --keypair")]
+    MissingFeePayer,
+    /// The transaction failed.
+    #[error("transaction failed: {0}")]
+    TransactionFailed(String),
+    /// The account is not an initialized nonce account.
+    #[error("account {0} is not an initialized nonce account")]
+    InvalidNonceAccount(Pubkey),
+    /// A keypair is not a required signer of the transaction.
+    #[error("{0} is not a required signer of the transaction")]
+    UnexpectedSigner(Pubkey),
+    /// A signature does not verify.
+    #[error("invalid signature for signer {0}")]
+    InvalidSignature(Pubkey),
+    /// Partially signed transactions are not copies of the same transaction.
+    #[error("the transactions to assemble have different messages")]
+    TransactionMismatch,
+    /// Required signers have not signed.
+    #[error("missing signatures from {}", join(.0))]
+    MissingSignatures(Vec<Pubkey>),
+}
+
+fn join(pubkeys: &[Pubkey]) -> String {
+    pubkeys
+        .iter()
+        .map(Pubkey::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl From<ClientError> for CliError {
+    fn from(e: ClientError) -> Self {
+        Self::Rpc(Box::new(e))
+    }
+}
+
+impl CliError {
+    pub(crate) fn invalid(kind: &'static str, message: impl ToString) -> Self {
+        Self::InvalidArgument {
+            kind,
+            message: message.to_string(),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! A command-line tool for managing config accounts.
+//!
+//! The `config` binary creates, shows, updates and compares config accounts
+//! through an RPC node. The commands themselves run against any [`Bank`],
+//! such as an in-process bank in tests. Transactions that several signers
+//! must sign can be signed offline, in parts, with [`offline`].
+
+pub mod bank;
+pub mod command;
+pub mod error;
+pub mod offline;
+pub mod payload;
+
+pub use {bank::Bank, error::CliError};
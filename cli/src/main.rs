@@ -0,0 +1,292 @@
+//! Manage config accounts from the command line.
+
+use {
+    clap::{Args, Parser, Subcommand},
+    solana_config_cli::{
+        command::{self, encode_transaction, DiffTarget, Outcome, TransactionOptions},
+        offline::{add_signatures, assemble, describe, read_transaction},
+        payload::{parse_key, PayloadSource, PayloadType},
+        Bank, CliError,
+    },
+    solana_hash::Hash,
+    solana_keypair::{read_keypair, Keypair},
+    solana_pubkey::Pubkey,
+    solana_rpc_client::rpc_client::RpcClient,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        process::ExitCode,
+    },
+};
+
+#[derive(Parser)]
+#[command(name = "config", version, about)]
+struct Cli {
+    /// The RPC URL of the cluster.
+    #[arg(long, short, global = true, default_value = "http://localhost:8899")]
+    url: String,
+    #[command(flatten)]
+    transaction: TransactionArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct TransactionArgs {
+    /// A keypair file to sign with. May be repeated.
+    #[arg(long = "keypair", short, global = true)]
+    keypairs: Vec<PathBuf>,
+    /// The fee payer, by default the first keypair.
+    #[arg(long, global = true)]
+    fee_payer: Option<Pubkey>,
+    /// The recent blockhash, by default the cluster's latest, or the
+    /// durable nonce stored in the nonce account.
+    #[arg(long, global = true)]
+    blockhash: Option<Hash>,
+    /// A nonce account, to make the transaction durable, so it can be
+    /// signed offline over any length of time.
+    #[arg(long, global = true)]
+    nonce: Option<Pubkey>,
+    /// The nonce authority, by default the fee payer.
+    #[arg(long, global = true, requires = "nonce")]
+    nonce_authority: Option<Pubkey>,
+    /// Print the transaction unsigned, as base64, for signing offline,
+    /// rather than signing and sending it.
+    #[arg(long, global = true)]
+    unsigned: bool,
+}
+
+#[derive(Args)]
+struct PayloadArgs {
+    /// The payload: hex:<hex>, base64:<base64>, json:<json>,
+    /// json-file:<path> or file:<path>.
+    #[arg(long)]
+    payload: PayloadSource,
+    /// The payload type, to encode JSON payloads and check others: raw,
+    /// validator-info or stake-config.
+    #[arg(long, default_value = "raw")]
+    payload_type: PayloadType,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a config account, and store its keys and initial payload.
+    Create {
+        /// The new config account, which must sign.
+        config: Pubkey,
+        /// A key to store: <pubkey>, <pubkey>:signer or threshold:<m>. May
+        /// be repeated. Signers must sign.
+        #[arg(long = "key", value_parser = parse_key)]
+        keys: Vec<(Pubkey, bool)>,
+        /// The largest payload the account will hold, in bytes.
+        #[arg(long)]
+        max_payload_len: u64,
+        /// The initial payload, empty by default.
+        #[arg(long)]
+        payload: Option<PayloadSource>,
+        /// The payload type, as for `store`.
+        #[arg(long, default_value = "raw")]
+        payload_type: PayloadType,
+    },
+    /// Show a config account's keys and payload.
+    Show {
+        /// The config account.
+        config: Pubkey,
+        /// Decode the payload as raw, validator-info or stake-config, rather
+        /// than the type its keys suggest.
+        #[arg(long)]
+        payload_type: Option<PayloadType>,
+    },
+    /// Store a new payload in a config account.
+    Store {
+        /// The config account.
+        config: Pubkey,
+        #[command(flatten)]
+        payload: PayloadArgs,
+        /// A new key, replacing the stored keys: <pubkey>, <pubkey>:signer
+        /// or threshold:<m>. May be repeated.
+        #[arg(long = "key", value_parser = parse_key)]
+        keys: Vec<(Pubkey, bool)>,
+        /// A stored signer that will sign, for accounts with a signer
+        /// threshold. May be repeated. By default, all stored signers.
+        #[arg(long = "signer")]
+        signers: Vec<Pubkey>,
+    },
+    /// Replace a config account's keys, such as to rotate its signers,
+    /// keeping its payload.
+    RotateSigners {
+        /// The config account.
+        config: Pubkey,
+        /// A new key: <pubkey>, <pubkey>:signer or threshold:<m>. May be
+        /// repeated.
+        #[arg(long = "key", value_parser = parse_key, required = true)]
+        keys: Vec<(Pubkey, bool)>,
+        /// A stored signer that will sign, for accounts with a signer
+        /// threshold. May be repeated. By default, all stored signers.
+        #[arg(long = "signer")]
+        signers: Vec<Pubkey>,
+    },
+    /// Compare a config account with another, or with the result of a
+    /// store.
+    Diff {
+        /// The config account.
+        config: Pubkey,
+        /// The account to compare with.
+        #[arg(required_unless_present = "payload", conflicts_with = "payload")]
+        other: Option<Pubkey>,
+        /// The payload to store, as for `store`.
+        #[arg(long)]
+        payload: Option<PayloadSource>,
+        /// The keys to store, as for `store`.
+        #[arg(long = "key", value_parser = parse_key, requires = "payload")]
+        keys: Vec<(Pubkey, bool)>,
+        /// Decode payloads as raw, validator-info or stake-config, rather
+        /// than the type their keys suggest.
+        #[arg(long)]
+        payload_type: Option<PayloadType>,
+    },
+    /// Add signatures to a transaction printed by `--unsigned` or `sign`,
+    /// with the given keypairs, and print it.
+    Sign {
+        /// The transaction, as base64 or file:<path>.
+        transaction: String,
+    },
+    /// Show what a partially signed transaction stores, and which signers
+    /// have yet to sign it.
+    Signers {
+        /// The transaction, as base64 or file:<path>.
+        transaction: String,
+    },
+    /// Merge partially signed copies of a transaction into the complete
+    /// transaction, and print it.
+    Assemble {
+        /// The copies, as base64 or file:<path>.
+        #[arg(required = true)]
+        transactions: Vec<String>,
+        /// Send the complete transaction rather than printing it.
+        #[arg(long)]
+        send: bool,
+    },
+}
+
+fn non_empty<T>(values: Vec<T>) -> Option<Vec<T>> {
+    (!values.is_empty()).then_some(values)
+}
+
+// Read a keypair file, in the JSON format written by `solana-keygen`.
+fn read_keypair_file(path: &Path) -> Result<Keypair, CliError> {
+    let contents = fs::read(path).map_err(|source| CliError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    read_keypair(&mut contents.as_slice()).map_err(|err| CliError::InvalidArgument {
+        kind: "keypair",
+        message: format!("{}: {err}", path.display()),
+    })
+}
+
+fn run(cli: Cli) -> Result<String, CliError> {
+    let bank = RpcClient::new(cli.url);
+    let options = TransactionOptions {
+        keypairs: cli
+            .transaction
+            .keypairs
+            .iter()
+            .map(|path| read_keypair_file(path))
+            .collect::<Result<_, _>>()?,
+        fee_payer: cli.transaction.fee_payer,
+        blockhash: cli.transaction.blockhash,
+        nonce: cli.transaction.nonce,
+        nonce_authority: cli.transaction.nonce_authority,
+        unsigned: cli.transaction.unsigned,
+    };
+
+    let outcome = match cli.command {
+        Command::Create {
+            config,
+            keys,
+            max_payload_len,
+            payload,
+            payload_type,
+        } => {
+            let payload = match payload {
+                Some(payload) => payload.to_bytes(payload_type)?,
+                None => vec![],
+            };
+            command::create(&bank, &options, &config, keys, max_payload_len, &payload)?
+        }
+        Command::Show {
+            config,
+            payload_type,
+        } => return command::show(&bank, &config, payload_type),
+        Command::Store {
+            config,
+            payload,
+            keys,
+            signers,
+        } => command::store(
+            &bank,
+            &options,
+            &config,
+            non_empty(keys),
+            &payload.payload.to_bytes(payload.payload_type)?,
+            non_empty(signers),
+        )?,
+        Command::RotateSigners {
+            config,
+            keys,
+            signers,
+        } => command::rotate_signers(&bank, &options, &config, keys, non_empty(signers))?,
+        Command::Diff {
+            config,
+            other,
+            payload,
+            keys,
+            payload_type,
+        } => {
+            let target = match (other, payload) {
+                (Some(other), _) => DiffTarget::Account(other),
+                (None, Some(payload)) => DiffTarget::Store {
+                    keys: non_empty(keys),
+                    payload: payload.to_bytes(payload_type.unwrap_or_default())?,
+                },
+                (None, None) => unreachable!("clap requires one of them"),
+            };
+            return command::diff(&bank, &config, target, payload_type);
+        }
+        Command::Sign { transaction } => {
+            let mut transaction = read_transaction(&transaction)?;
+            add_signatures(&mut transaction, &options.keypairs)?;
+            return Ok(format!("{}\n", encode_transaction(&transaction)));
+        }
+        Command::Signers { transaction } => return describe(&read_transaction(&transaction)?),
+        Command::Assemble { transactions, send } => {
+            let copies = transactions
+                .iter()
+                .map(|transaction| read_transaction(transaction))
+                .collect::<Result<Vec<_>, _>>()?;
+            let transaction = assemble(&copies)?;
+            if !send {
+                return Ok(format!("{}\n", encode_transaction(&transaction)));
+            }
+            Outcome::Sent(Bank::send_transaction(&bank, &transaction)?)
+        }
+    };
+    Ok(match outcome {
+        Outcome::Sent(signature) => format!("Signature: {signature}\n"),
+        Outcome::Unsigned(transaction) => format!("{}\n", encode_transaction(&transaction)),
+    })
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! Signing transactions offline, in parts.
+//!
+//! When a config account has several stored signers, each may sign on a
+//! separate machine. The transaction is built once, unsigned, with a durable
+//! nonce or a blockhash that is still recent when it's sent. Each signer adds
+//! their signatures to a copy with [`add_signatures`], [`required_signers`]
+//! reports who has yet to sign, and [`assemble`] merges the copies into the
+//! complete transaction.
+
+use {
+    crate::{bank::Bank, command::decode_transaction, error::CliError, payload::format_key},
+    solana_config_interface::{id, instruction::parse_store_instruction, validate::StoreError},
+    solana_hash::Hash,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_message::Message,
+    solana_nonce::{state::State, versions::Versions},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::SystemInstruction,
+    solana_transaction::Transaction,
+    std::{fmt::Write, fs},
+};
+
+/// Read a transaction argument: base64, as printed by `--unsigned` and
+/// `sign`, or `file:<path>` for a file holding it.
+pub fn read_transaction(arg: &str) -> Result<Transaction, CliError> {
+    match arg.strip_prefix("file:") {
+        Some(path) => {
+            let encoded = fs::read_to_string(path).map_err(|source| CliError::Io {
+                path: path.into(),
+                source,
+            })?;
+            decode_transaction(&encoded)
+        }
+        None => decode_transaction(arg),
+    }
+}
+
+/// Fetch the durable nonce stored in the nonce account `nonce`, to use as
+/// the blockhash of a transaction, checking that `authority` may advance it.
+pub fn fetch_nonce(bank: &impl Bank, nonce: &Pubkey, authority: &Pubkey) -> Result<Hash, CliError> {
+    let account = bank
+        .get_account(nonce)?
+        .ok_or(CliError::AccountNotFound(*nonce))?;
+    if account.owner != solana_system_interface::program::id() {
+        return Err(CliError::InvalidNonceAccount(*nonce));
+    }
+    // Legacy nonces can't make transactions durable.
+    let Ok(Versions::Current(state)) = bincode::deserialize::<Versions>(&account.data) else {
+        return Err(CliError::InvalidNonceAccount(*nonce));
+    };
+    let State::Initialized(data) = *state else {
+        return Err(CliError::InvalidNonceAccount(*nonce));
+    };
+    let stored_authority = Pubkey::new_from_array(data.authority.to_bytes());
+    if stored_authority != *authority {
+        return Err(CliError::invalid(
+            "nonce authority",
+            format!("{authority}; nonce account {nonce} has authority {stored_authority}"),
+        ));
+    }
+    Ok(data.blockhash())
+}
+
+/// The instructions of a sanitized message.
+pub fn instructions(message: &Message) -> Vec<Instruction> {
+    message
+        .instructions
+        .iter()
+        .map(|instruction| Instruction {
+            program_id: message.account_keys[usize::from(instruction.program_id_index)],
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|&index| {
+                    let index = usize::from(index);
+                    AccountMeta {
+                        pubkey: message.account_keys[index],
+                        is_signer: message.is_signer(index),
+                        is_writable: message.is_maybe_writable(index, None),
+                    }
+                })
+                .collect(),
+            data: instruction.data.clone(),
+        })
+        .collect()
+}
+
+// The nonce account and authority, if the first instruction advances a
+// durable nonce.
+fn durable_nonce(instructions: &[Instruction]) -> Option<(Pubkey, Pubkey)> {
+    let instruction = instructions.first()?;
+    let advance = bincode::serialize(&SystemInstruction::AdvanceNonceAccount).unwrap();
+    if instruction.program_id != solana_system_interface::program::id()
+        || instruction.data != advance
+    {
+        return None;
+    }
+    match instruction.accounts.as_slice() {
+        [nonce, _, authority, ..] => Some((nonce.pubkey, authority.pubkey)),
+        _ => None,
+    }
+}
+
+// The accounts signing config instructions: for a `store`, the accounts
+// signing for its signer keys, in the order of the keys, and for other
+// instructions, every signer other than the config account.
+fn config_signers(instructions: &[Instruction]) -> Result<Vec<Pubkey>, CliError> {
+    let mut signers = vec![];
+    for instruction in instructions.iter().filter(|ix| ix.program_id == id()) {
+        let accounts = match parse_store_instruction(instruction) {
+            Ok(store) => store.signers,
+            Err(StoreError::NotStore) => instruction.accounts.iter().skip(1).collect(),
+            Err(err) => return Err(err.into()),
+        };
+        for account in accounts.into_iter().filter(|account| account.is_signer) {
+            if !signers.contains(&account.pubkey) {
+                signers.push(account.pubkey);
+            }
+        }
+    }
+    Ok(signers)
+}
+
+/// Whether a required signer has signed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureStatus {
+    /// The signature verifies.
+    Signed,
+    /// There is no signature yet.
+    Missing,
+    /// The signature doesn't verify, such as because it signs another
+    /// message.
+    Invalid,
+}
+
+/// A signer that a transaction requires.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RequiredSigner {
+    /// The signer.
+    pub pubkey: Pubkey,
+    /// Whether it has signed.
+    pub status: SignatureStatus,
+    /// Whether it pays the fee.
+    pub is_fee_payer: bool,
+    /// Whether it advances the durable nonce.
+    pub is_nonce_authority: bool,
+    /// Whether it signs a config instruction for a signer key.
+    pub is_config_signer: bool,
+}
+
+// The status of the signature in each required signer's slot.
+fn signature_statuses(transaction: &Transaction) -> Vec<(Pubkey, SignatureStatus)> {
+    let message_data = transaction.message_data();
+    let num_signers = usize::from(transaction.message.header.num_required_signatures);
+    transaction.message.account_keys[..num_signers]
+        .iter()
+        .zip(&transaction.signatures)
+        .map(|(pubkey, signature)| {
+            let status = if *signature == Signature::default() {
+                SignatureStatus::Missing
+            } else if signature.verify(pubkey.as_ref(), &message_data) {
+                SignatureStatus::Signed
+            } else {
+                SignatureStatus::Invalid
+            };
+            (*pubkey, status)
+        })
+        .collect()
+}
+
+/// The signers that `transaction` requires: the fee payer, the nonce
+/// authority, then the config signers in the order of the signer keys, as
+/// `instruction::store` lists them, then any others.
+///
+/// The message sorts its account keys, so it doesn't keep this order itself.
+pub fn required_signers(transaction: &Transaction) -> Result<Vec<RequiredSigner>, CliError> {
+    let instructions = instructions(&transaction.message);
+    let nonce_authority = durable_nonce(&instructions).map(|(_, authority)| authority);
+    let config_signers = config_signers(&instructions)?;
+    let statuses = signature_statuses(transaction);
+
+    let mut order = statuses
+        .first()
+        .map(|(pubkey, _)| *pubkey)
+        .into_iter()
+        .collect::<Vec<_>>();
+    for pubkey in nonce_authority
+        .iter()
+        .chain(&config_signers)
+        .chain(statuses.iter().map(|(pubkey, _)| pubkey))
+    {
+        if !order.contains(pubkey) {
+            order.push(*pubkey);
+        }
+    }
+    Ok(order
+        .into_iter()
+        .filter_map(|pubkey| {
+            let (i, (_, status)) = statuses
+                .iter()
+                .enumerate()
+                .find(|(_, (signer, _))| *signer == pubkey)?;
+            Some(RequiredSigner {
+                pubkey,
+                status: *status,
+                is_fee_payer: i == 0,
+                is_nonce_authority: nonce_authority == Some(pubkey),
+                is_config_signer: config_signers.contains(&pubkey),
+            })
+        })
+        .collect())
+}
+
+/// The config signers of `transaction` that have yet to sign it.
+pub fn missing_config_signers(transaction: &Transaction) -> Result<Vec<Pubkey>, CliError> {
+    Ok(required_signers(transaction)?
+        .into_iter()
+        .filter(|signer| signer.is_config_signer && signer.status != SignatureStatus::Signed)
+        .map(|signer| signer.pubkey)
+        .collect())
+}
+
+/// Sign `transaction` with `keypairs`, each of which must be a required
+/// signer, leaving the other signatures as they are.
+pub fn add_signatures<S: Signer>(
+    transaction: &mut Transaction,
+    keypairs: &[S],
+) -> Result<(), CliError> {
+    let message_data = transaction.message_data();
+    let num_signers = usize::from(transaction.message.header.num_required_signatures);
+    for keypair in keypairs {
+        let pubkey = keypair.pubkey();
+        let index = transaction.message.account_keys[..num_signers]
+            .iter()
+            .position(|signer| *signer == pubkey)
+            .ok_or(CliError::UnexpectedSigner(pubkey))?;
+        transaction.signatures[index] = keypair.try_sign_message(&message_data)?;
+    }
+    Ok(())
+}
+
+/// Merge the signatures of partially signed copies of a transaction into
+/// the complete transaction.
+///
+/// Every signature is verified, and every required signer must have signed
+/// one of the copies.
+pub fn assemble(copies: &[Transaction]) -> Result<Transaction, CliError> {
+    let (first, rest) = copies
+        .split_first()
+        .ok_or_else(|| CliError::invalid("transaction", "nothing to assemble"))?;
+    if rest.iter().any(|copy| copy.message != first.message) {
+        return Err(CliError::TransactionMismatch);
+    }
+
+    let mut transaction = first.clone();
+    for copy in copies {
+        for (i, (pubkey, status)) in signature_statuses(copy).into_iter().enumerate() {
+            match status {
+                SignatureStatus::Signed => transaction.signatures[i] = copy.signatures[i],
+                SignatureStatus::Missing => {}
+                SignatureStatus::Invalid => return Err(CliError::InvalidSignature(pubkey)),
+            }
+        }
+    }
+
+    let missing = required_signers(&transaction)?
+        .into_iter()
+        .filter(|signer| signer.status != SignatureStatus::Signed)
+        .map(|signer| signer.pubkey)
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(CliError::MissingSignatures(missing));
+    }
+    Ok(transaction)
+}
+
+/// Describe a partially signed transaction: what it stores, and which
+/// signers have signed.
+pub fn describe(transaction: &Transaction) -> Result<String, CliError> {
+    let instructions = instructions(&transaction.message);
+    let mut output = String::new();
+    match durable_nonce(&instructions) {
+        Some((nonce, _)) => writeln!(
+            output,
+            "Durable nonce: {} (nonce account {nonce})",
+            transaction.message.recent_blockhash
+        ),
+        None => writeln!(
+            output,
+            "Blockhash: {}",
+            transaction.message.recent_blockhash
+        ),
+    }
+    .unwrap();
+
+    for store in instructions
+        .iter()
+        .filter_map(|instruction| parse_store_instruction(instruction).ok())
+    {
+        writeln!(output, "Store in {}:", store.config_account.pubkey).unwrap();
+        writeln!(output, "  Keys:").unwrap();
+        for key in &store.keys.to_config_keys().keys {
+            writeln!(output, "    {}", format_key(key)).unwrap();
+        }
+        writeln!(output, "  Payload: {}", hex::encode(store.data)).unwrap();
+    }
+
+    let signers = required_signers(transaction)?;
+    writeln!(output, "Signers:").unwrap();
+    for signer in &signers {
+        let status = match signer.status {
+            SignatureStatus::Signed => "signed",
+            SignatureStatus::Missing => "missing",
+            SignatureStatus::Invalid => "invalid",
+        };
+        let roles = [
+            (signer.is_fee_payer, "fee payer"),
+            (signer.is_nonce_authority, "nonce authority"),
+            (signer.is_config_signer, "config signer"),
+        ]
+        .iter()
+        .filter(|(is_role, _)| *is_role)
+        .map(|(_, role)| *role)
+        .collect::<Vec<_>>();
+        write!(output, "  {} {status}", signer.pubkey).unwrap();
+        if !roles.is_empty() {
+            write!(output, " ({})", roles.join(", ")).unwrap();
+        }
+        writeln!(output).unwrap();
+    }
+    let signed = signers
+        .iter()
+        .filter(|signer| signer.status == SignatureStatus::Signed)
+        .count();
+    writeln!(output, "Signed: {signed} of {}", signers.len()).unwrap();
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::command::encode_transaction, solana_config_interface::instruction::store,
+        solana_keypair::Keypair,
+    };
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::new_from_array([seed; 32])
+    }
+
+    // A store signed by two stored signers, listed after a non-signer key.
+    fn unsigned_store() -> Transaction {
+        let keys = vec![
+            (Pubkey::new_unique(), false),
+            (keypair(3).pubkey(), true),
+            (keypair(2).pubkey(), true),
+        ];
+        let instruction = store(&Pubkey::new_unique(), false, keys, &[7u8; 2]);
+        let message = Message::new_with_blockhash(
+            &[instruction],
+            Some(&keypair(1).pubkey()),
+            &Hash::new_from_array([5; 32]),
+        );
+        Transaction::new_unsigned(message)
+    }
+
+    #[test]
+    fn test_required_signers() {
+        let mut transaction = unsigned_store();
+        let signers = required_signers(&transaction).unwrap();
+        // The fee payer, then the stored signers in the order of the keys.
+        assert_eq!(
+            signers
+                .iter()
+                .map(|signer| signer.pubkey)
+                .collect::<Vec<_>>(),
+            [
+                keypair(1).pubkey(),
+                keypair(3).pubkey(),
+                keypair(2).pubkey()
+            ]
+        );
+        assert_eq!(
+            signers
+                .iter()
+                .map(|signer| (signer.is_fee_payer, signer.is_config_signer))
+                .collect::<Vec<_>>(),
+            [(true, false), (false, true), (false, true)]
+        );
+        assert!(signers
+            .iter()
+            .all(|signer| signer.status == SignatureStatus::Missing && !signer.is_nonce_authority));
+
+        add_signatures(&mut transaction, &[keypair(1), keypair(2)]).unwrap();
+        assert_eq!(
+            missing_config_signers(&transaction).unwrap(),
+            [keypair(3).pubkey()]
+        );
+        assert!(matches!(
+            add_signatures(&mut transaction, &[keypair(4)]),
+            Err(CliError::UnexpectedSigner(pubkey)) if pubkey == keypair(4).pubkey()
+        ));
+
+        let output = describe(&transaction).unwrap();
+        assert!(output.starts_with(&format!("Blockhash: {}\n", Hash::new_from_array([5; 32]))));
+        assert!(output.contains("  Payload: 0707\n"));
+        assert!(output.contains(&format!(
+            "  {} missing (config signer)\n",
+            keypair(3).pubkey()
+        )));
+        assert!(output.contains(&format!("  {} signed (fee payer)\n", keypair(1).pubkey())));
+        assert!(output.ends_with("Signed: 2 of 3\n"));
+    }
+
+    #[test]
+    fn test_assemble() {
+        let unsigned = unsigned_store();
+        let copies = (1..=3)
+            .map(|seed| {
+                let mut copy = unsigned.clone();
+                add_signatures(&mut copy, &[keypair(seed)]).unwrap();
+                copy
+            })
+            .collect::<Vec<_>>();
+
+        let transaction = assemble(&copies).unwrap();
+        transaction.verify().unwrap();
+        assert!(matches!(
+            assemble(&copies[..2]),
+            Err(CliError::MissingSignatures(missing)) if missing == [keypair(3).pubkey()]
+        ));
+        assert!(assemble(&[]).is_err());
+
+        let mut other = unsigned_store();
+        add_signatures(&mut other, &[keypair(3)]).unwrap();
+        assert!(matches!(
+            assemble(&[copies[0].clone(), copies[1].clone(), other.clone()]),
+            Err(CliError::TransactionMismatch)
+        ));
+
+        // A signature over another message.
+        let mut forged = copies[2].clone();
+        forged.signatures = other.signatures.clone();
+        assert_eq!(
+            required_signers(&forged).unwrap()[1].status,
+            SignatureStatus::Invalid
+        );
+        assert!(matches!(
+            assemble(&[copies[0].clone(), copies[1].clone(), forged]),
+            Err(CliError::InvalidSignature(pubkey)) if pubkey == keypair(3).pubkey()
+        ));
+    }
+
+    #[test]
+    fn test_read_transaction() {
+        let transaction = unsigned_store();
+        let encoded = encode_transaction(&transaction);
+        assert_eq!(read_transaction(&encoded).unwrap(), transaction);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transaction");
+        fs::write(&path, format!("{encoded}\n")).unwrap();
+        assert_eq!(
+            read_transaction(&format!("file:{}", path.display())).unwrap(),
+            transaction
+        );
+
+        let mut unsanitized = transaction.clone();
+        unsanitized.signatures.pop();
+        assert!(read_transaction(&encode_transaction(&unsanitized)).is_err());
+        unsanitized.signatures = transaction.signatures.clone();
+        unsanitized.message.instructions[0].program_id_index = 9;
+        assert!(read_transaction(&encode_transaction(&unsanitized)).is_err());
+        assert!(read_transaction("file:/nonexistent").is_err());
+    }
+}
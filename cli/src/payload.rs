@@ -0,0 +1,331 @@
+//! Payload and key arguments.
+//!
+//! A payload is the data stored after the keys of a config account. It can be
+//! given as raw bytes, in hex, base64 or a file, or as JSON for a well-known
+//! payload type, which is encoded with bincode as the program's callers do.
+
+use {
+    crate::error::CliError,
+    base64::{prelude::BASE64_STANDARD, Engine},
+    serde::{ser::SerializeTuple, Serialize, Serializer},
+    solana_config_interface::{
+        stake_config::{self, StakeConfig},
+        state::{parse_threshold_marker, threshold_marker},
+        validator_info::{self, ValidatorInfo},
+    },
+    solana_pubkey::Pubkey,
+    std::{fs, path::PathBuf, str::FromStr},
+};
+
+/// A well-known payload type, which can be given and shown as JSON.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum PayloadType {
+    /// Raw bytes.
+    #[default]
+    Raw,
+    /// A `ValidatorInfo`.
+    ValidatorInfo,
+    /// A `StakeConfig`.
+    StakeConfig,
+}
+
+impl FromStr for PayloadType {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Ok(Self::Raw),
+            "validator-info" => Ok(Self::ValidatorInfo),
+            "stake-config" => Ok(Self::StakeConfig),
+            _ => Err(CliError::invalid(
+                "payload type",
+                format!("{s}; expected raw, validator-info or stake-config"),
+            )),
+        }
+    }
+}
+
+impl PayloadType {
+    /// The type of the payload stored with `keys` in the config account at
+    /// `address`, from the layout of the keys.
+    pub fn detect(address: &Pubkey, keys: &[(Pubkey, bool)]) -> Self {
+        if validator_info::identity(keys).is_some() {
+            Self::ValidatorInfo
+        } else if *address == stake_config::ID && keys == stake_config::keys().as_slice() {
+            Self::StakeConfig
+        } else {
+            Self::Raw
+        }
+    }
+
+    /// Decode `bytes` as this type, and return it as pretty-printed JSON.
+    ///
+    /// Bytes after the payload, such as unused account space, are ignored.
+    pub fn to_json(self, bytes: &[u8]) -> Result<Option<String>, CliError> {
+        let invalid = |err: bincode::Error| CliError::invalid("payload", err);
+        let json = match self {
+            Self::Raw => return Ok(None),
+            Self::ValidatorInfo => {
+                let info = bincode::deserialize::<ValidatorInfo>(bytes).map_err(invalid)?;
+                info.check_size()?;
+                serde_json::to_string_pretty(&info)
+            }
+            Self::StakeConfig => {
+                let stake_config = bincode::deserialize::<StakeConfig>(bytes).map_err(invalid)?;
+                stake_config.check()?;
+                serde_json::to_string_pretty(&stake_config)
+            }
+        };
+        json.map(Some)
+            .map_err(|err| CliError::invalid("payload", err))
+    }
+
+    fn encode_json(self, json: &str) -> Result<Vec<u8>, CliError> {
+        let invalid = |err: serde_json::Error| CliError::invalid("JSON payload", err);
+        match self {
+            Self::Raw => Err(CliError::invalid(
+                "payload",
+                "JSON payloads need a payload type",
+            )),
+            Self::ValidatorInfo => {
+                let info = serde_json::from_str::<ValidatorInfo>(json).map_err(invalid)?;
+                info.check_size()?;
+                Ok(bincode::serialize(&info).unwrap())
+            }
+            Self::StakeConfig => {
+                let stake_config = serde_json::from_str::<StakeConfig>(json).map_err(invalid)?;
+                stake_config.check()?;
+                Ok(bincode::serialize(&stake_config).unwrap())
+            }
+        }
+    }
+}
+
+/// Where a payload comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayloadSource {
+    /// `hex:<bytes>`
+    Hex(String),
+    /// `base64:<bytes>`
+    Base64(String),
+    /// `json:<json>`, encoded with bincode as a well-known payload type.
+    Json(String),
+    /// `json-file:<path>`, a file holding JSON, as for `json:`.
+    JsonFile(PathBuf),
+    /// `file:<path>`, a file holding the raw bytes.
+    File(PathBuf),
+}
+
+impl FromStr for PayloadSource {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once(':').ok_or_else(|| {
+            CliError::invalid(
+                "payload",
+                "expected hex:, base64:, json:, json-file: or file: followed by the payload",
+            )
+        })?;
+        match kind {
+            "hex" => Ok(Self::Hex(value.to_string())),
+            "base64" => Ok(Self::Base64(value.to_string())),
+            "json" => Ok(Self::Json(value.to_string())),
+            "json-file" => Ok(Self::JsonFile(value.into())),
+            "file" => Ok(Self::File(value.into())),
+            _ => Err(CliError::invalid(
+                "payload",
+                format!("unknown payload encoding {kind:?}"),
+            )),
+        }
+    }
+}
+
+fn read_file(path: &PathBuf) -> Result<Vec<u8>, CliError> {
+    fs::read(path).map_err(|source| CliError::Io {
+        path: path.clone(),
+        source,
+    })
+}
+
+impl PayloadSource {
+    /// The payload bytes.
+    ///
+    /// JSON is encoded as `payload_type`. Raw bytes are checked to decode as
+    /// `payload_type`, unless it's [`PayloadType::Raw`].
+    pub fn to_bytes(&self, payload_type: PayloadType) -> Result<Vec<u8>, CliError> {
+        let bytes = match self {
+            Self::Hex(hex) => {
+                hex::decode(hex.trim()).map_err(|err| CliError::invalid("hex payload", err))?
+            }
+            Self::Base64(base64) => BASE64_STANDARD
+                .decode(base64.trim())
+                .map_err(|err| CliError::invalid("base64 payload", err))?,
+            Self::Json(json) => return payload_type.encode_json(json),
+            Self::JsonFile(path) => {
+                let json = String::from_utf8(read_file(path)?)
+                    .map_err(|err| CliError::invalid("JSON payload", err))?;
+                return payload_type.encode_json(&json);
+            }
+            Self::File(path) => read_file(path)?,
+        };
+        payload_type.to_json(&bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Payload bytes that serialize as themselves, with no length prefix, so
+/// they can be passed to `instruction::store`.
+pub struct RawPayload<'a>(pub &'a [u8]);
+
+impl Serialize for RawPayload<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for byte in self.0 {
+            tuple.serialize_element(byte)?;
+        }
+        tuple.end()
+    }
+}
+
+/// Parse a key argument: `<pubkey>` for a non-signer key, `<pubkey>:signer`
+/// for a signer key, or `threshold:<m>` for the marker key requiring `m` of
+/// the signers.
+pub fn parse_key(s: &str) -> Result<(Pubkey, bool), CliError> {
+    let invalid = |message: String| CliError::invalid("key", message);
+    if let Some(threshold) = s.strip_prefix("threshold:") {
+        let threshold = threshold
+            .parse::<u8>()
+            .map_err(|err| invalid(format!("{s}: {err}")))?;
+        return Ok((threshold_marker(threshold), false));
+    }
+    let (pubkey, is_signer) = match s.strip_suffix(":signer") {
+        Some(pubkey) => (pubkey, true),
+        None => (s, false),
+    };
+    let pubkey = pubkey
+        .parse::<Pubkey>()
+        .map_err(|err| invalid(format!("{s}: {err}")))?;
+    Ok((pubkey, is_signer))
+}
+
+/// Format a key as [`parse_key`] reads it.
+pub fn format_key(&(pubkey, is_signer): &(Pubkey, bool)) -> String {
+    match parse_threshold_marker(&pubkey) {
+        Some(threshold) if !is_signer => format!("threshold:{threshold}"),
+        _ if is_signer => format!("{pubkey}:signer"),
+        _ => pubkey.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_config_interface::state::ConfigKeys};
+
+    #[test]
+    fn test_payload_sources() {
+        let bytes = vec![1, 2, 3, 0xFF];
+        for source in ["hex:010203ff", "base64:AQID/w=="] {
+            let source = source.parse::<PayloadSource>().unwrap();
+            assert_eq!(source.to_bytes(PayloadType::Raw).unwrap(), bytes);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payload");
+        fs::write(&path, &bytes).unwrap();
+        let source = format!("file:{}", path.display())
+            .parse::<PayloadSource>()
+            .unwrap();
+        assert_eq!(source.to_bytes(PayloadType::Raw).unwrap(), bytes);
+
+        for source in ["010203", "hex", "utf8:abc"] {
+            assert!(source.parse::<PayloadSource>().is_err());
+        }
+        for source in ["hex:0g", "base64:!"] {
+            let source = source.parse::<PayloadSource>().unwrap();
+            assert!(source.to_bytes(PayloadType::Raw).is_err());
+        }
+    }
+
+    #[test]
+    fn test_json_payloads() {
+        let source =
+            r#"json:{"info":"{\"name\":\"Validator\"}"}"#.parse::<PayloadSource>().unwrap();
+        let info = ValidatorInfo {
+            info: r#"{"name":"Validator"}"#.to_string(),
+        };
+        let bytes = source.to_bytes(PayloadType::ValidatorInfo).unwrap();
+        assert_eq!(bytes, bincode::serialize(&info).unwrap());
+        assert!(source.to_bytes(PayloadType::Raw).is_err());
+        assert_eq!(
+            PayloadType::ValidatorInfo.to_json(&bytes).unwrap(),
+            Some(serde_json::to_string_pretty(&info).unwrap())
+        );
+
+        let source = r#"json:{"warmup_cooldown_rate":0.25,"slash_penalty":12}"#
+            .parse::<PayloadSource>()
+            .unwrap();
+        assert_eq!(
+            source.to_bytes(PayloadType::StakeConfig).unwrap(),
+            bincode::serialize(&StakeConfig::default()).unwrap()
+        );
+        assert!(source.to_bytes(PayloadType::ValidatorInfo).is_err());
+
+        // Well-known payloads are checked.
+        let source = r#"json:{"warmup_cooldown_rate":2.0,"slash_penalty":12}"#
+            .parse::<PayloadSource>()
+            .unwrap();
+        assert!(matches!(
+            source.to_bytes(PayloadType::StakeConfig),
+            Err(CliError::Payload(_))
+        ));
+        let source = "hex:0100000000000000".parse::<PayloadSource>().unwrap();
+        assert!(source.to_bytes(PayloadType::ValidatorInfo).is_err());
+    }
+
+    #[test]
+    fn test_raw_payload() {
+        let keys = ConfigKeys {
+            keys: vec![(Pubkey::new_unique(), true)],
+        };
+        let bytes = [7u8; 5];
+        let mut expected = bincode::serialize(&keys).unwrap();
+        expected.extend_from_slice(&bytes);
+        assert_eq!(
+            bincode::serialize(&(&keys, RawPayload(&bytes))).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        let pubkey = Pubkey::new_unique();
+        for key in [
+            (pubkey, false),
+            (pubkey, true),
+            (threshold_marker(2), false),
+        ] {
+            assert_eq!(parse_key(&format_key(&key)).unwrap(), key);
+        }
+        assert_eq!(format_key(&(threshold_marker(2), false)), "threshold:2");
+        for key in ["", "abc", "threshold:x", &format!("{pubkey}:writable")] {
+            assert!(parse_key(key).is_err());
+        }
+
+        assert_eq!(
+            PayloadType::detect(&pubkey, &validator_info::keys(&pubkey)),
+            PayloadType::ValidatorInfo
+        );
+        assert_eq!(
+            PayloadType::detect(&stake_config::ID, &stake_config::keys()),
+            PayloadType::StakeConfig
+        );
+        assert_eq!(
+            PayloadType::detect(&pubkey, &stake_config::keys()),
+            PayloadType::Raw
+        );
+        assert_eq!(
+            PayloadType::detect(&pubkey, &[(pubkey, true)]),
+            PayloadType::Raw
+        );
+    }
+}
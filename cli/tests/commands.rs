@@ -0,0 +1,559 @@
+//! Commands run against an in-process bank, executing the regression program
+//! in `program/fuzz/program-mb.so`.
+//!
+//! The regression program predates the extension instructions, so
+//! `rotate-signers` transactions are only built and preflighted, not
+//! executed. Executing `SetKeys` is covered by the program's functional tests
+//! instead; nothing here checks that the program accepts what the CLI builds.
+
+use {
+    mollusk_svm::{program::loader_keys::LOADER_V3, MolluskContext},
+    solana_account::Account,
+    solana_config_cli::{
+        command::{
+            self, decode_transaction, encode_transaction, sign_transaction, DiffTarget, Outcome,
+            TransactionOptions,
+        },
+        offline::{add_signatures, assemble, instructions, missing_config_signers},
+        payload::{format_key, PayloadType},
+        Bank, CliError,
+    },
+    solana_config_interface::{
+        id,
+        instruction::set_keys,
+        stake_config::{self, StakeConfig},
+        state::{threshold_marker, ConfigKeys, FROZEN_MARKER},
+        validate::StoreError,
+    },
+    solana_hash::Hash,
+    solana_keypair::Keypair,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_signer::Signer,
+    solana_system_interface::instruction::SystemInstruction,
+    solana_transaction::Transaction,
+    std::collections::HashMap,
+};
+
+const BLOCKHASH: Hash = Hash::new_from_array([7; 32]);
+
+// Where a nonce account stores its durable nonce.
+const NONCE_OFFSET: std::ops::Range<usize> = 40..72;
+
+struct MolluskBank(MolluskContext<HashMap<Pubkey, Account>>);
+
+impl MolluskBank {
+    fn new(payer: &Pubkey) -> Self {
+        let elf = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../program/fuzz/program-mb.so"
+        ))
+        .expect("failed to read program/fuzz/program-mb.so");
+        let mut mollusk = mollusk_svm::Mollusk::default();
+        mollusk.add_program_with_loader_and_elf(&id(), &LOADER_V3, &elf);
+        let accounts = HashMap::from([(
+            *payer,
+            Account::new(10_000_000_000, 0, &solana_system_interface::program::id()),
+        )]);
+        Self(mollusk.with_context(accounts))
+    }
+
+    fn insert(&self, pubkey: Pubkey, account: Account) {
+        self.0.account_store.borrow_mut().insert(pubkey, account);
+    }
+}
+
+impl Bank for MolluskBank {
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, CliError> {
+        let store = self.0.account_store.borrow();
+        Ok(store
+            .get(pubkey)
+            .filter(|account| account.lamports > 0)
+            .cloned())
+    }
+
+    fn minimum_balance(&self, space: usize) -> Result<u64, CliError> {
+        Ok(self.0.mollusk.sysvars.rent.minimum_balance(space))
+    }
+
+    fn latest_blockhash(&self) -> Result<Hash, CliError> {
+        Ok(BLOCKHASH)
+    }
+
+    fn send_transaction(&self, transaction: &Transaction) -> Result<Signature, CliError> {
+        transaction
+            .verify()
+            .map_err(|err| CliError::TransactionFailed(err.to_string()))?;
+        let mut instructions = instructions(&transaction.message);
+        let blockhash = transaction.message.recent_blockhash;
+        let advance = bincode::serialize(&SystemInstruction::AdvanceNonceAccount).unwrap();
+        if instructions.first().map(|instruction| &instruction.data) == Some(&advance) {
+            // Mollusk has no recent blockhashes for the system program to
+            // advance the nonce with, so advance it here, as the runtime
+            // would.
+            let nonce = instructions.remove(0).accounts[0].pubkey;
+            let mut store = self.0.account_store.borrow_mut();
+            let durable_nonce = &mut store.get_mut(&nonce).unwrap().data[NONCE_OFFSET];
+            if durable_nonce != blockhash.as_ref() {
+                return Err(CliError::TransactionFailed("stale nonce".to_string()));
+            }
+            durable_nonce.copy_from_slice(BLOCKHASH.as_ref());
+        } else if blockhash != BLOCKHASH {
+            return Err(CliError::TransactionFailed(
+                "blockhash not found".to_string(),
+            ));
+        }
+        let result = self.0.process_instruction_chain(&instructions);
+        if result.program_result.is_err() {
+            return Err(CliError::TransactionFailed(format!(
+                "{:?}",
+                result.program_result
+            )));
+        }
+        Ok(transaction.signatures[0])
+    }
+}
+
+fn keypair(seed: u8) -> Keypair {
+    Keypair::new_from_array([seed; 32])
+}
+
+fn options(keypairs: Vec<Keypair>) -> TransactionOptions {
+    TransactionOptions {
+        keypairs,
+        ..TransactionOptions::default()
+    }
+}
+
+#[test]
+fn test_create_store_show_diff() {
+    let (payer, config, signer) = (keypair(1), keypair(2), keypair(3));
+    let bank = MolluskBank::new(&payer.pubkey());
+    let keys = vec![(signer.pubkey(), true), (Pubkey::new_unique(), false)];
+
+    let outcome = command::create(
+        &bank,
+        &options(vec![payer, keypair(2), keypair(3)]),
+        &config.pubkey(),
+        keys.clone(),
+        16,
+        &[1, 2, 3],
+    )
+    .unwrap();
+    assert!(matches!(outcome, Outcome::Sent(_)));
+
+    let account = bank.get_account(&config.pubkey()).unwrap().unwrap();
+    assert_eq!(account.owner, id());
+    assert_eq!(account.data.len(), 1 + 2 * 33 + 16);
+    assert_eq!(
+        account.lamports,
+        bank.minimum_balance(account.data.len()).unwrap()
+    );
+    assert!(matches!(
+        command::create(
+            &bank,
+            &options(vec![keypair(1), keypair(2), keypair(3)]),
+            &config.pubkey(),
+            keys.clone(),
+            16,
+            &[],
+        ),
+        Err(CliError::AccountExists(_))
+    ));
+
+    let output = command::show(&bank, &config.pubkey(), None).unwrap();
+    assert!(output.contains(&format!(
+        "Keys:\n  {}\n  {}\n",
+        format_key(&keys[0]),
+        format_key(&keys[1])
+    )));
+    assert!(output.contains(&format!("Payload: 010203{}\n", "00".repeat(13))));
+
+    // The stored signer signs updates, but the config account needn't.
+    let diff = command::diff(
+        &bank,
+        &config.pubkey(),
+        DiffTarget::Store {
+            keys: None,
+            payload: vec![1, 9, 9, 4],
+        },
+        None,
+    )
+    .unwrap();
+    assert_eq!(diff, "~ payload bytes 1..4: 020300 -> 090904\n");
+    command::store(
+        &bank,
+        &options(vec![keypair(1), keypair(3)]),
+        &config.pubkey(),
+        None,
+        &[1, 9, 9, 4],
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        command::diff(
+            &bank,
+            &config.pubkey(),
+            DiffTarget::Store {
+                keys: None,
+                payload: vec![1, 9, 9, 4],
+            },
+            None,
+        )
+        .unwrap(),
+        "No differences\n"
+    );
+
+    // Checked before sending.
+    assert!(matches!(
+        command::store(
+            &bank,
+            &options(vec![keypair(1)]),
+            &config.pubkey(),
+            None,
+            &[],
+            None,
+        ),
+        Err(CliError::MissingKeypair(pubkey)) if pubkey == signer.pubkey()
+    ));
+    assert!(matches!(
+        command::store(
+            &bank,
+            &options(vec![keypair(1), keypair(3)]),
+            &config.pubkey(),
+            None,
+            &[0; 17],
+            None,
+        ),
+        Err(CliError::Store(StoreError::InstructionDataTooLarge))
+    ));
+    assert!(matches!(
+        command::store(
+            &bank,
+            &options(vec![keypair(1), keypair(3)]),
+            &config.pubkey(),
+            Some(vec![(keypair(4).pubkey(), true)]),
+            &[],
+            None,
+        ),
+        Err(CliError::Store(StoreError::MissingSignerAccount(_)))
+            | Err(CliError::Store(StoreError::UnknownSigner(_)))
+    ));
+}
+
+#[test]
+fn test_unsigned_transactions() {
+    let (payer, config) = (keypair(1), keypair(2));
+    let bank = MolluskBank::new(&payer.pubkey());
+
+    let options = TransactionOptions {
+        fee_payer: Some(payer.pubkey()),
+        unsigned: true,
+        ..TransactionOptions::default()
+    };
+    let Outcome::Unsigned(transaction) =
+        command::create(&bank, &options, &config.pubkey(), vec![], 8, &[]).unwrap()
+    else {
+        panic!("expected an unsigned transaction");
+    };
+    assert_eq!(transaction.message.recent_blockhash, BLOCKHASH);
+    assert_eq!(
+        transaction.message.account_keys[..2],
+        [payer.pubkey(), config.pubkey()]
+    );
+    assert!(transaction
+        .signatures
+        .iter()
+        .all(|signature| *signature == Signature::default()));
+    assert!(bank.send_transaction(&transaction).is_err());
+
+    // Signed offline, then sent.
+    let mut transaction = decode_transaction(&encode_transaction(&transaction)).unwrap();
+    assert!(matches!(
+        sign_transaction(&mut transaction, &[keypair(1)]),
+        Err(CliError::MissingKeypair(pubkey)) if pubkey == config.pubkey()
+    ));
+    sign_transaction(&mut transaction, &[payer, config]).unwrap();
+    bank.send_transaction(&transaction).unwrap();
+    assert!(bank.get_account(&keypair(2).pubkey()).unwrap().is_some());
+}
+
+#[test]
+fn test_rotate_signers() {
+    let bank = MolluskBank::new(&keypair(1).pubkey());
+    let config = Pubkey::new_unique();
+    let (old_signer, new_signer) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let keys = vec![
+        (old_signer, true),
+        (Pubkey::new_unique(), true),
+        (threshold_marker(1), false),
+    ];
+    let account = |keys: &[(Pubkey, bool)], extra_space: usize| {
+        let mut data = bincode::serialize(&ConfigKeys {
+            keys: keys.to_vec(),
+        })
+        .unwrap();
+        data.resize(data.len() + extra_space, 0);
+        Account {
+            lamports: 1_000_000,
+            data,
+            owner: id(),
+            executable: false,
+            rent_epoch: 0,
+        }
+    };
+    bank.insert(config, account(&keys, 33));
+
+    let options = TransactionOptions {
+        fee_payer: Some(keypair(1).pubkey()),
+        unsigned: true,
+        ..TransactionOptions::default()
+    };
+    let new_keys = vec![
+        (new_signer, true),
+        (keys[1].0, true),
+        (threshold_marker(1), false),
+        (Pubkey::new_unique(), false),
+    ];
+    let Outcome::Unsigned(transaction) = command::rotate_signers(
+        &bank,
+        &options,
+        &config,
+        new_keys.clone(),
+        Some(vec![old_signer]),
+    )
+    .unwrap() else {
+        panic!("expected an unsigned transaction");
+    };
+    let expected = set_keys(&config, new_keys.clone(), &[old_signer]);
+    assert_eq!(transaction.message.instructions[0].data, expected.data);
+    assert_eq!(transaction.message.header.num_required_signatures, 2);
+    assert_eq!(transaction.message.account_keys[1], old_signer);
+
+    // Only stored signers can sign.
+    assert!(matches!(
+        command::rotate_signers(
+            &bank,
+            &options,
+            &config,
+            new_keys.clone(),
+            Some(vec![new_signer]),
+        ),
+        Err(CliError::Store(StoreError::UnknownSigner(_)))
+    ));
+
+    // No room for a second new key.
+    let mut longer_keys = new_keys.clone();
+    longer_keys.push((Pubkey::new_unique(), false));
+    assert!(matches!(
+        command::rotate_signers(&bank, &options, &config, longer_keys, None),
+        Err(CliError::Space(_))
+    ));
+
+    // Fewer signers than the stored threshold.
+    assert!(matches!(
+        command::rotate_signers(&bank, &options, &config, new_keys.clone(), Some(vec![])),
+        Err(CliError::Store(StoreError::TooFewSigners))
+    ));
+
+    // The new keys must pass the same checks as `store` keys.
+    let duplicate_keys = vec![(new_signer, true), (new_signer, true)];
+    assert!(matches!(
+        command::rotate_signers(&bank, &options, &config, duplicate_keys, None),
+        Err(CliError::Store(StoreError::DuplicateKeys))
+    ));
+    let bad_threshold_keys = vec![(new_signer, true), (threshold_marker(2), false)];
+    assert!(matches!(
+        command::rotate_signers(&bank, &options, &config, bad_threshold_keys, None),
+        Err(CliError::Store(StoreError::InvalidThreshold))
+    ));
+
+    // Without a threshold, every stored signer must sign.
+    bank.insert(config, account(&keys[..2], 33));
+    assert!(matches!(
+        command::rotate_signers(
+            &bank,
+            &options,
+            &config,
+            new_keys.clone(),
+            Some(vec![old_signer]),
+        ),
+        Err(CliError::Store(StoreError::MissingSignature(signer))) if signer == keys[1].0
+    ));
+
+    // With no stored signers, the config account signs.
+    bank.insert(config, account(&[], 33));
+    let Outcome::Unsigned(transaction) =
+        command::rotate_signers(&bank, &options, &config, vec![(new_signer, true)], None).unwrap()
+    else {
+        panic!("expected an unsigned transaction");
+    };
+    assert_eq!(transaction.message.account_keys[1], config);
+
+    let mut frozen_keys = keys.clone();
+    frozen_keys.push((FROZEN_MARKER, true));
+    bank.insert(config, account(&frozen_keys, 33));
+    assert!(matches!(
+        command::rotate_signers(&bank, &options, &config, new_keys, None),
+        Err(CliError::Store(StoreError::AccountFrozen))
+    ));
+}
+
+#[test]
+fn test_show_and_diff_well_known_payloads() {
+    let bank = MolluskBank::new(&keypair(1).pubkey());
+    let stake_config_account = |stake_config: &StakeConfig| Account {
+        lamports: 1_000_000,
+        data: stake_config::account_data(stake_config).unwrap(),
+        owner: id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    let (current, other) = (stake_config::ID, Pubkey::new_unique());
+    bank.insert(current, stake_config_account(&StakeConfig::default()));
+    bank.insert(
+        other,
+        stake_config_account(&StakeConfig {
+            slash_penalty: 13,
+            ..StakeConfig::default()
+        }),
+    );
+
+    let output = command::show(&bank, &current, None).unwrap();
+    assert!(output.contains("\"slash_penalty\": 12"), "{output}");
+    // Only the account at the stake config address is detected as one.
+    let output = command::show(&bank, &other, None).unwrap();
+    assert!(!output.contains("slash_penalty"), "{output}");
+    assert_eq!(
+        command::diff(
+            &bank,
+            &current,
+            DiffTarget::Account(other),
+            Some(PayloadType::StakeConfig)
+        )
+        .unwrap(),
+        concat!(
+            "- {\n",
+            "-   \"warmup_cooldown_rate\": 0.25,\n",
+            "-   \"slash_penalty\": 12\n",
+            "- }\n",
+            "+ {\n",
+            "+   \"warmup_cooldown_rate\": 0.25,\n",
+            "+   \"slash_penalty\": 13\n",
+            "+ }\n",
+        )
+    );
+
+    let not_config = Pubkey::new_unique();
+    bank.insert(not_config, Account::new(1, 0, &Pubkey::new_unique()));
+    assert!(matches!(
+        command::show(&bank, &not_config, None),
+        Err(CliError::InvalidConfigAccount(_))
+    ));
+    assert!(matches!(
+        command::show(&bank, &Pubkey::new_unique(), None),
+        Err(CliError::AccountNotFound(_))
+    ));
+}
+
+// A nonce account, as the system program stores it.
+fn nonce_account(authority: &Pubkey, durable_nonce: &Hash) -> Account {
+    let mut data = vec![1, 0, 0, 0, 1, 0, 0, 0];
+    data.extend_from_slice(authority.as_ref());
+    data.extend_from_slice(durable_nonce.as_ref());
+    data.extend_from_slice(&5000u64.to_le_bytes());
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner: solana_system_interface::program::id(),
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[test]
+fn test_offline_multisig_store() {
+    let (payer, config) = (keypair(1), keypair(2));
+    let (custodian_a, custodian_b) = (keypair(3), keypair(4));
+    let bank = MolluskBank::new(&payer.pubkey());
+    let keys = vec![(custodian_a.pubkey(), true), (custodian_b.pubkey(), true)];
+    command::create(
+        &bank,
+        &options(vec![keypair(1), keypair(2), keypair(3), keypair(4)]),
+        &config.pubkey(),
+        keys,
+        8,
+        &[],
+    )
+    .unwrap();
+
+    let nonce = Pubkey::new_unique();
+    let durable_nonce = Hash::new_from_array([9; 32]);
+    bank.insert(nonce, nonce_account(&payer.pubkey(), &durable_nonce));
+    let store_with_nonce = |nonce_authority| {
+        let options = TransactionOptions {
+            fee_payer: Some(keypair(1).pubkey()),
+            nonce: Some(nonce),
+            nonce_authority,
+            unsigned: true,
+            ..TransactionOptions::default()
+        };
+        command::store(&bank, &options, &keypair(2).pubkey(), None, &[4, 2], None)
+    };
+    assert!(matches!(
+        store_with_nonce(Some(custodian_a.pubkey())),
+        Err(CliError::InvalidArgument { .. })
+    ));
+
+    // Built online, unsigned, with a durable nonce.
+    let Outcome::Unsigned(unsigned) = store_with_nonce(None).unwrap() else {
+        panic!("expected an unsigned transaction");
+    };
+    assert_eq!(unsigned.message.recent_blockhash, durable_nonce);
+    let encoded = encode_transaction(&unsigned);
+
+    // Each signs their own copy, offline.
+    let signed_copy = |keypair: Keypair| {
+        let mut transaction = decode_transaction(&encoded).unwrap();
+        add_signatures(&mut transaction, &[keypair]).unwrap();
+        encode_transaction(&transaction)
+    };
+    let copies = [signed_copy(keypair(1)), signed_copy(keypair(3))];
+    let partial = assemble(
+        &copies
+            .iter()
+            .map(|copy| decode_transaction(copy).unwrap())
+            .collect::<Vec<_>>(),
+    );
+    assert!(matches!(
+        partial,
+        Err(CliError::MissingSignatures(missing)) if missing == [custodian_b.pubkey()]
+    ));
+    let transaction = decode_transaction(&copies[1]).unwrap();
+    assert_eq!(
+        missing_config_signers(&transaction).unwrap(),
+        [custodian_b.pubkey()]
+    );
+
+    let copies = [
+        decode_transaction(&copies[0]).unwrap(),
+        transaction,
+        decode_transaction(&signed_copy(keypair(4))).unwrap(),
+    ];
+    let transaction = assemble(&copies).unwrap();
+    assert!(missing_config_signers(&transaction).unwrap().is_empty());
+    bank.send_transaction(&transaction).unwrap();
+    let account = bank.get_account(&config.pubkey()).unwrap().unwrap();
+    assert_eq!(account.data[1 + 2 * 33..], [4, 2, 0, 0, 0, 0, 0, 0]);
+
+    // The nonce advanced, so the transaction can't be replayed.
+    assert!(bank.send_transaction(&transaction).is_err());
+
+    let mut uninitialized = nonce_account(&payer.pubkey(), &durable_nonce);
+    uninitialized.data[4] = 0;
+    bank.insert(nonce, uninitialized);
+    assert!(matches!(
+        store_with_nonce(None),
+        Err(CliError::InvalidNonceAccount(pubkey)) if pubkey == nonce
+    ));
+}
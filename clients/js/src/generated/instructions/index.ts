@@ -6,4 +6,10 @@
  * @see https://github.com/codama-idl/codama
  */
 
+export * from './close';
+export * from './compareAndStore';
+export * from './freeze';
+export * from './resize';
+export * from './setKeys';
 export * from './store';
+export * from './write';
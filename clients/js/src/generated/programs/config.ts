@@ -7,7 +7,11 @@
  */
 
 import {
+    assertIsInstructionWithAccounts,
+    containsBytes,
     extendClient,
+    getU32Encoder,
+    getU8Encoder,
     type Address,
     type ClientWithRpc,
     type ClientWithTransactionPlanning,
@@ -15,6 +19,9 @@ import {
     type ExtendedClient,
     type GetAccountInfoApi,
     type GetMultipleAccountsApi,
+    type Instruction,
+    type InstructionWithData,
+    type ReadonlyUint8Array,
 } from '@solana/kit';
 import {
     addSelfFetchFunctions,
@@ -23,7 +30,35 @@ import {
     type SelfPlanAndSendFunctions,
 } from '@solana/kit/program-client-core';
 import { getConfigCodec, type Config, type ConfigArgs } from '../accounts';
-import { getStoreInstruction, type ParsedStoreInstruction, type StoreInput } from '../instructions';
+import {
+    getCloseInstruction,
+    getCompareAndStoreInstruction,
+    getFreezeInstruction,
+    getResizeInstruction,
+    getSetKeysInstruction,
+    getStoreInstruction,
+    getWriteInstruction,
+    parseCloseInstruction,
+    parseCompareAndStoreInstruction,
+    parseFreezeInstruction,
+    parseResizeInstruction,
+    parseSetKeysInstruction,
+    parseWriteInstruction,
+    type CloseInput,
+    type CompareAndStoreInput,
+    type FreezeInput,
+    type ParsedCloseInstruction,
+    type ParsedCompareAndStoreInstruction,
+    type ParsedFreezeInstruction,
+    type ParsedResizeInstruction,
+    type ParsedSetKeysInstruction,
+    type ParsedStoreInstruction,
+    type ParsedWriteInstruction,
+    type ResizeInput,
+    type SetKeysInput,
+    type StoreInput,
+    type WriteInput,
+} from '../instructions';
 
 export const CONFIG_PROGRAM_ADDRESS =
     'Config1111111111111111111111111111111111111' as Address<'Config1111111111111111111111111111111111111'>;
@@ -34,11 +69,84 @@ export enum ConfigAccount {
 
 export enum ConfigInstruction {
     Store,
+    Close,
+    Resize,
+    Write,
+    CompareAndStore,
+    SetKeys,
+    Freeze,
+}
+
+export function identifyConfigInstruction(
+    instruction: { data: ReadonlyUint8Array } | ReadonlyUint8Array,
+): ConfigInstruction {
+    const data = 'data' in instruction ? instruction.data : instruction;
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(0), 1)) {
+        return ConfigInstruction.Close;
+    }
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(1), 1)) {
+        return ConfigInstruction.Resize;
+    }
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(2), 1)) {
+        return ConfigInstruction.Write;
+    }
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(3), 1)) {
+        return ConfigInstruction.CompareAndStore;
+    }
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(4), 1)) {
+        return ConfigInstruction.SetKeys;
+    }
+    if (containsBytes(data, getU8Encoder().encode(255), 0) && containsBytes(data, getU32Encoder().encode(5), 1)) {
+        return ConfigInstruction.Freeze;
+    }
+    throw new Error('The provided instruction could not be identified as a config instruction.');
 }
 
-export type ParsedConfigInstruction<TProgram extends string = 'Config1111111111111111111111111111111111111'> = {
-    instructionType: ConfigInstruction.Store;
-} & ParsedStoreInstruction<TProgram>;
+export type ParsedConfigInstruction<TProgram extends string = 'Config1111111111111111111111111111111111111'> =
+    | ({ instructionType: ConfigInstruction.Store } & ParsedStoreInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.Close } & ParsedCloseInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.Resize } & ParsedResizeInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.Write } & ParsedWriteInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.CompareAndStore } & ParsedCompareAndStoreInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.SetKeys } & ParsedSetKeysInstruction<TProgram>)
+    | ({ instructionType: ConfigInstruction.Freeze } & ParsedFreezeInstruction<TProgram>);
+
+export function parseConfigInstruction<TProgram extends string>(
+    instruction: Instruction<TProgram> & InstructionWithData<ReadonlyUint8Array>,
+): ParsedConfigInstruction<TProgram> {
+    const instructionType = identifyConfigInstruction(instruction);
+    switch (instructionType) {
+        case ConfigInstruction.Close: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: ConfigInstruction.Close, ...parseCloseInstruction(instruction) };
+        }
+        case ConfigInstruction.Resize: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: ConfigInstruction.Resize, ...parseResizeInstruction(instruction) };
+        }
+        case ConfigInstruction.Write: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: ConfigInstruction.Write, ...parseWriteInstruction(instruction) };
+        }
+        case ConfigInstruction.CompareAndStore: {
+            assertIsInstructionWithAccounts(instruction);
+            return {
+                instructionType: ConfigInstruction.CompareAndStore,
+                ...parseCompareAndStoreInstruction(instruction),
+            };
+        }
+        case ConfigInstruction.SetKeys: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: ConfigInstruction.SetKeys, ...parseSetKeysInstruction(instruction) };
+        }
+        case ConfigInstruction.Freeze: {
+            assertIsInstructionWithAccounts(instruction);
+            return { instructionType: ConfigInstruction.Freeze, ...parseFreezeInstruction(instruction) };
+        }
+        default:
+            throw new Error(`Unrecognized instruction type: ${instructionType as string}`);
+    }
+}
 
 export type ConfigPlugin = { accounts: ConfigPluginAccounts; instructions: ConfigPluginInstructions };
 
@@ -48,6 +156,14 @@ export type ConfigPluginAccounts = {
 
 export type ConfigPluginInstructions = {
     store: (input: StoreInput) => ReturnType<typeof getStoreInstruction> & SelfPlanAndSendFunctions;
+    close: (input: CloseInput) => ReturnType<typeof getCloseInstruction> & SelfPlanAndSendFunctions;
+    resize: (input: ResizeInput) => ReturnType<typeof getResizeInstruction> & SelfPlanAndSendFunctions;
+    write: (input: WriteInput) => ReturnType<typeof getWriteInstruction> & SelfPlanAndSendFunctions;
+    compareAndStore: (
+        input: CompareAndStoreInput,
+    ) => ReturnType<typeof getCompareAndStoreInstruction> & SelfPlanAndSendFunctions;
+    setKeys: (input: SetKeysInput) => ReturnType<typeof getSetKeysInstruction> & SelfPlanAndSendFunctions;
+    freeze: (input: FreezeInput) => ReturnType<typeof getFreezeInstruction> & SelfPlanAndSendFunctions;
 };
 
 export type ConfigPluginRequirements = ClientWithRpc<GetAccountInfoApi & GetMultipleAccountsApi> &
@@ -59,7 +175,15 @@ export function configProgram() {
         return extendClient(client, {
             config: <ConfigPlugin>{
                 accounts: { config: addSelfFetchFunctions(client, getConfigCodec()) },
-                instructions: { store: input => addSelfPlanAndSendFunctions(client, getStoreInstruction(input)) },
+                instructions: {
+                    store: input => addSelfPlanAndSendFunctions(client, getStoreInstruction(input)),
+                    close: input => addSelfPlanAndSendFunctions(client, getCloseInstruction(input)),
+                    resize: input => addSelfPlanAndSendFunctions(client, getResizeInstruction(input)),
+                    write: input => addSelfPlanAndSendFunctions(client, getWriteInstruction(input)),
+                    compareAndStore: input => addSelfPlanAndSendFunctions(client, getCompareAndStoreInstruction(input)),
+                    setKeys: input => addSelfPlanAndSendFunctions(client, getSetKeysInstruction(input)),
+                    freeze: input => addSelfPlanAndSendFunctions(client, getFreezeInstruction(input)),
+                },
             },
         });
     };
@@ -3,3 +3,7 @@
 //! to add features, then rerun codama to update it.
 //!
 //! <https://github.com/codama-idl/codama>
+
+pub(crate) mod r#config;
+
+pub use self::r#config::ConfigError;
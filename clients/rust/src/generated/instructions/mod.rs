@@ -4,6 +4,18 @@
 //!
 //! <https://github.com/codama-idl/codama>
 
+pub(crate) mod r#close;
+pub(crate) mod r#compare_and_store;
+pub(crate) mod r#freeze;
+pub(crate) mod r#resize;
+pub(crate) mod r#set_keys;
 pub(crate) mod r#store;
+pub(crate) mod r#write;
 
+pub use self::r#close::*;
+pub use self::r#compare_and_store::*;
+pub use self::r#freeze::*;
+pub use self::r#resize::*;
+pub use self::r#set_keys::*;
 pub use self::r#store::*;
+pub use self::r#write::*;
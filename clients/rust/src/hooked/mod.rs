@@ -1,3 +1,12 @@
+#[cfg(feature = "fetch")]
+pub mod nonblocking;
+#[cfg(feature = "fetch")]
+pub mod scan;
 mod short_vec;
+mod store_cpi;
+#[cfg(feature = "validator-info")]
+mod validator_info;
 
 pub use short_vec::*;
+#[cfg(feature = "validator-info")]
+pub use validator_info::*;
@@ -0,0 +1,236 @@
+//! Async counterparts of the `fetch` helpers, using the nonblocking RPC
+//! client.
+//!
+//! Addresses are fetched with `get_multiple_accounts`, in batches of at most
+//! `MAX_MULTIPLE_ACCOUNTS` addresses each, one batch after the other.
+
+use {
+    crate::{
+        accounts::Config,
+        shared::{DecodedAccount, MaybeAccount},
+    },
+    solana_address::Address,
+    solana_rpc_client::{
+        api::{client_error::Error as ClientError, request::MAX_MULTIPLE_ACCOUNTS},
+        nonblocking::rpc_client::RpcClient,
+    },
+};
+
+/// Errors returned when fetching config accounts.
+#[derive(Debug, thiserror::Error)]
+pub enum FetchError {
+    /// The RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    /// The RPC node returned a different number of accounts than requested.
+    #[error("Requested {expected} accounts, but received {received}")]
+    AccountCountMismatch {
+        /// The number of accounts requested.
+        expected: usize,
+        /// The number of accounts received.
+        received: usize,
+    },
+    /// The account does not exist.
+    #[error("Account not found: {0}")]
+    AccountNotFound(Address),
+    /// The account data is not a valid config account.
+    #[error("Invalid config account data for {address}: {source}")]
+    InvalidAccountData {
+        /// The account's address.
+        address: Address,
+        /// The decoding error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl From<ClientError> for FetchError {
+    fn from(e: ClientError) -> Self {
+        Self::Rpc(Box::new(e))
+    }
+}
+
+/// Fetches and decodes a config account, failing if it doesn't exist.
+pub async fn fetch_config(
+    rpc: &RpcClient,
+    address: &Address,
+) -> Result<DecodedAccount<Config>, FetchError> {
+    let mut accounts = fetch_all_config(rpc, &[*address]).await?;
+    Ok(accounts.remove(0))
+}
+
+/// Fetches and decodes config accounts, failing if any of them doesn't
+/// exist.
+pub async fn fetch_all_config(
+    rpc: &RpcClient,
+    addresses: &[Address],
+) -> Result<Vec<DecodedAccount<Config>>, FetchError> {
+    fetch_all_maybe_config(rpc, addresses)
+        .await?
+        .into_iter()
+        .map(|account| match account {
+            MaybeAccount::Exists(account) => Ok(account),
+            MaybeAccount::NotFound(address) => Err(FetchError::AccountNotFound(address)),
+        })
+        .collect()
+}
+
+/// Fetches and decodes a config account that may not exist.
+pub async fn fetch_maybe_config(
+    rpc: &RpcClient,
+    address: &Address,
+) -> Result<MaybeAccount<Config>, FetchError> {
+    let mut accounts = fetch_all_maybe_config(rpc, &[*address]).await?;
+    Ok(accounts.remove(0))
+}
+
+/// Fetches and decodes config accounts that may not exist.
+pub async fn fetch_all_maybe_config(
+    rpc: &RpcClient,
+    addresses: &[Address],
+) -> Result<Vec<MaybeAccount<Config>>, FetchError> {
+    let mut decoded_accounts = Vec::with_capacity(addresses.len());
+    for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc.get_multiple_accounts(batch).await?;
+        if accounts.len() != batch.len() {
+            return Err(FetchError::AccountCountMismatch {
+                expected: batch.len(),
+                received: accounts.len(),
+            });
+        }
+        for (address, account) in batch.iter().zip(accounts) {
+            let Some(account) = account else {
+                decoded_accounts.push(MaybeAccount::NotFound(*address));
+                continue;
+            };
+            let data = Config::from_bytes(&account.data).map_err(|source| {
+                FetchError::InvalidAccountData {
+                    address: *address,
+                    source,
+                }
+            })?;
+            decoded_accounts.push(MaybeAccount::Exists(DecodedAccount {
+                address: *address,
+                account,
+                data,
+            }));
+        }
+    }
+    Ok(decoded_accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        serde_json::{json, Value},
+        solana_rpc_client::{api::request::RpcRequest, mock_sender::MocksMap},
+    };
+
+    fn address(seed: u8) -> Address {
+        Address::from([seed; 32])
+    }
+
+    // A `getMultipleAccounts` response. Existing accounts hold `data`,
+    // base64-encoded.
+    fn response(accounts: &[bool], data: &str) -> Value {
+        let value = accounts
+            .iter()
+            .map(|exists| {
+                exists.then(|| {
+                    json!({
+                        "lamports": 1,
+                        "data": [data, "base64"],
+                        "owner": crate::ID.to_string(),
+                        "executable": false,
+                        "rentEpoch": 0,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        json!({ "context": { "slot": 1 }, "value": value })
+    }
+
+    fn mock_rpc(responses: impl IntoIterator<Item = Value>) -> RpcClient {
+        let mocks = responses
+            .into_iter()
+            .map(|response| (RpcRequest::GetMultipleAccounts, response))
+            .collect::<MocksMap>();
+        RpcClient::new_mock_with_mocks_map("succeeds", mocks)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_batches() {
+        // An empty config account: no keys and no data.
+        let empty_config = "AA==";
+        let addresses = (0..150).map(|i| address(i as u8)).collect::<Vec<_>>();
+        let mut exists = [true; 150];
+        exists[120] = false;
+
+        let rpc = mock_rpc([
+            response(&exists[..100], empty_config),
+            response(&exists[100..], empty_config),
+        ]);
+        let accounts = fetch_all_maybe_config(&rpc, &addresses).await.unwrap();
+        assert_eq!(accounts.len(), 150);
+        for (i, account) in accounts.iter().enumerate() {
+            match account {
+                MaybeAccount::Exists(account) => {
+                    assert_ne!(i, 120);
+                    assert_eq!(account.address, addresses[i]);
+                    assert!(account.data.keys.keys.is_empty());
+                }
+                MaybeAccount::NotFound(address) => {
+                    assert_eq!(i, 120);
+                    assert_eq!(*address, addresses[i]);
+                }
+            }
+        }
+
+        let rpc = mock_rpc([
+            response(&exists[..100], empty_config),
+            response(&exists[100..], empty_config),
+        ]);
+        assert!(matches!(
+            fetch_all_config(&rpc, &addresses).await,
+            Err(FetchError::AccountNotFound(address)) if address == addresses[120],
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_errors() {
+        let address = address(1);
+
+        let rpc = mock_rpc([response(&[true], "AA==")]);
+        let account = fetch_config(&rpc, &address).await.unwrap();
+        assert_eq!(account.address, address);
+
+        let rpc = mock_rpc([response(&[false], "")]);
+        assert!(matches!(
+            fetch_maybe_config(&rpc, &address).await,
+            Ok(MaybeAccount::NotFound(not_found)) if not_found == address,
+        ));
+
+        // Config keys can't be decoded from empty data.
+        let rpc = mock_rpc([response(&[true], "")]);
+        assert!(matches!(
+            fetch_config(&rpc, &address).await,
+            Err(FetchError::InvalidAccountData { address: invalid, .. }) if invalid == address,
+        ));
+
+        let rpc = mock_rpc([response(&[true, true], "AA==")]);
+        assert!(matches!(
+            fetch_config(&rpc, &address).await,
+            Err(FetchError::AccountCountMismatch {
+                expected: 1,
+                received: 2,
+            }),
+        ));
+
+        let rpc = RpcClient::new_mock("fails".to_string());
+        assert!(matches!(
+            fetch_config(&rpc, &address).await,
+            Err(FetchError::Rpc(_)),
+        ));
+    }
+}
@@ -0,0 +1,355 @@
+//! Finding config accounts by their keys or payload with
+//! `getProgramAccounts`.
+//!
+//! Config account data starts with the `ShortU16` length of the key list,
+//! which is a single byte for fewer than 128 keys, followed by 33 bytes per
+//! key: the address and the signer flag. The payload follows the keys.
+//!
+//! `memcmp` filters compare bytes at fixed offsets, so finding a key anywhere
+//! in the key list takes one query per position, each a full scan of the
+//! program's accounts by the RPC node. Every match is decoded as a
+//! [`Config`] and checked again, since a filter may also match payload bytes
+//! of an account with fewer keys. Accounts that don't decode are skipped.
+
+use {
+    crate::{accounts::Config, shared::DecodedAccount},
+    solana_account::Account,
+    solana_account_decoder_client_types::UiAccountEncoding,
+    solana_address::Address,
+    solana_config_interface::instruction::MAX_STORE_KEYS,
+    solana_rpc_client::{
+        api::{
+            client_error::Error as ClientError,
+            config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+            filter::{Memcmp, RpcFilterType},
+        },
+        rpc_client::RpcClient,
+    },
+    std::collections::BTreeSet,
+};
+
+/// The size of a key entry in the key list.
+const KEY_LEN: usize = 33;
+
+/// The largest key list a single length byte can prefix.
+const MAX_SINGLE_BYTE_KEYS: u8 = 0x7F;
+
+/// Errors returned when scanning for config accounts.
+#[derive(Debug, thiserror::Error)]
+pub enum ScanError {
+    /// The RPC request failed.
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<ClientError>),
+    /// The key count takes more than one byte to encode, so it can't be
+    /// filtered on.
+    #[error("Can't filter on {0} keys, at most {MAX_SINGLE_BYTE_KEYS} are supported")]
+    TooManyKeys(u8),
+}
+
+impl From<ClientError> for ScanError {
+    fn from(e: ClientError) -> Self {
+        Self::Rpc(Box::new(e))
+    }
+}
+
+/// A source of program accounts, such as an RPC node.
+pub trait ProgramAccountsSource {
+    /// Returns the accounts owned by `program_id` that pass every filter.
+    fn program_accounts(
+        &self,
+        program_id: &Address,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Address, Account)>, ClientError>;
+}
+
+impl ProgramAccountsSource for RpcClient {
+    fn program_accounts(
+        &self,
+        program_id: &Address,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Address, Account)>, ClientError> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        Ok(self
+            .get_program_ui_accounts_with_config(program_id, config)?
+            .into_iter()
+            .filter_map(|(address, account)| Some((address, account.decode()?)))
+            .collect())
+    }
+}
+
+/// An in-memory stand-in for an RPC node, applying filters the same way.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryAccounts {
+    /// The accounts, of any owner.
+    pub accounts: Vec<(Address, Account)>,
+}
+
+impl ProgramAccountsSource for InMemoryAccounts {
+    fn program_accounts(
+        &self,
+        program_id: &Address,
+        filters: Vec<RpcFilterType>,
+    ) -> Result<Vec<(Address, Account)>, ClientError> {
+        Ok(self
+            .accounts
+            .iter()
+            .filter(|(_, account)| account.owner == *program_id)
+            .filter(|(_, account)| {
+                filters.iter().all(|filter| match filter {
+                    RpcFilterType::DataSize(size) => account.data.len() as u64 == *size,
+                    RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(&account.data),
+                    RpcFilterType::TokenAccountState => false,
+                })
+            })
+            .cloned()
+            .collect())
+    }
+}
+
+/// A filter for configs with exactly `count` keys.
+///
+/// Fails if `count` is 128 or more, since the length is then encoded in more
+/// than one byte.
+pub fn key_count_filter(count: u8) -> Result<RpcFilterType, ScanError> {
+    if count > MAX_SINGLE_BYTE_KEYS {
+        return Err(ScanError::TooManyKeys(count));
+    }
+    Ok(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![count])))
+}
+
+/// A filter for configs with `key` at `index` in their key list, optionally
+/// with the given signer flag.
+pub fn key_filter(index: u8, key: &Address, is_signer: Option<bool>) -> RpcFilterType {
+    let mut bytes = key.to_bytes().to_vec();
+    bytes.extend(is_signer.map(u8::from));
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(key_offset(index), bytes))
+}
+
+/// Filters for configs with `key_count` keys whose payload contains `bytes`
+/// at `offset`, such as a discriminator.
+pub fn payload_filters(
+    key_count: u8,
+    offset: usize,
+    bytes: &[u8],
+) -> Result<Vec<RpcFilterType>, ScanError> {
+    Ok(vec![
+        key_count_filter(key_count)?,
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            key_offset(key_count) + offset,
+            bytes.to_vec(),
+        )),
+    ])
+}
+
+/// Finds the configs that pass every filter.
+pub fn find_configs<S: ProgramAccountsSource + ?Sized>(
+    source: &S,
+    filters: Vec<RpcFilterType>,
+) -> Result<Vec<DecodedAccount<Config>>, ScanError> {
+    Ok(source
+        .program_accounts(&crate::ID, filters)?
+        .into_iter()
+        .filter_map(|(address, account)| {
+            let data = Config::from_bytes(&account.data).ok()?;
+            Some(DecodedAccount {
+                address,
+                account,
+                data,
+            })
+        })
+        .collect())
+}
+
+/// Finds the configs that list `key`, as a signer or not, among their first
+/// `max_keys` keys, or the first [`MAX_STORE_KEYS`] if `None`.
+///
+/// Each key position searched costs a separate `getProgramAccounts` request,
+/// and so a full scan of the program's accounts, so keep `max_keys` small.
+/// Accounts written by `store` never have more than [`MAX_STORE_KEYS`] keys.
+pub fn find_configs_by_key<S: ProgramAccountsSource + ?Sized>(
+    source: &S,
+    key: &Address,
+    max_keys: Option<u8>,
+) -> Result<Vec<DecodedAccount<Config>>, ScanError> {
+    find_configs_by_key_entry(source, key, None, max_keys)
+}
+
+/// Finds the configs that list `signer` as a signer among their first
+/// `max_keys` keys, or the first [`MAX_STORE_KEYS`] if `None`.
+///
+/// As with [`find_configs_by_key`], each key position searched costs a
+/// separate `getProgramAccounts` request.
+pub fn find_configs_by_signer<S: ProgramAccountsSource + ?Sized>(
+    source: &S,
+    signer: &Address,
+    max_keys: Option<u8>,
+) -> Result<Vec<DecodedAccount<Config>>, ScanError> {
+    find_configs_by_key_entry(source, signer, Some(true), max_keys)
+}
+
+/// Finds the configs with `key_count` keys whose payload contains `bytes` at
+/// `offset`.
+pub fn find_configs_by_payload<S: ProgramAccountsSource + ?Sized>(
+    source: &S,
+    key_count: u8,
+    offset: usize,
+    bytes: &[u8],
+) -> Result<Vec<DecodedAccount<Config>>, ScanError> {
+    let mut configs = find_configs(source, payload_filters(key_count, offset, bytes)?)?;
+    configs.retain(|config| {
+        config.data.keys.keys.len() == usize::from(key_count)
+            && config
+                .data
+                .data
+                .get(offset..)
+                .is_some_and(|payload| payload.starts_with(bytes))
+    });
+    Ok(configs)
+}
+
+fn find_configs_by_key_entry<S: ProgramAccountsSource + ?Sized>(
+    source: &S,
+    key: &Address,
+    is_signer: Option<bool>,
+    max_keys: Option<u8>,
+) -> Result<Vec<DecodedAccount<Config>>, ScanError> {
+    // `MAX_STORE_KEYS` always fits in a single length byte.
+    let max_keys = max_keys.unwrap_or(MAX_STORE_KEYS as u8);
+    let mut addresses = BTreeSet::new();
+    let mut configs = Vec::new();
+    for index in 0..max_keys.min(MAX_SINGLE_BYTE_KEYS) {
+        for config in find_configs(source, vec![key_filter(index, key, is_signer)])? {
+            let listed = config.data.keys.keys.get(usize::from(index)).is_some_and(
+                |(listed_key, listed_is_signer)| {
+                    listed_key == key && is_signer.is_none_or(|flag| flag == *listed_is_signer)
+                },
+            );
+            if listed && addresses.insert(config.address) {
+                configs.push(config);
+            }
+        }
+    }
+    Ok(configs)
+}
+
+fn key_offset(index: u8) -> usize {
+    1 + usize::from(index) * KEY_LEN
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::ConfigKeys};
+
+    fn address(seed: u8) -> Address {
+        Address::from([seed; 32])
+    }
+
+    fn config_account(keys: Vec<(Address, bool)>, data: Vec<u8>) -> Account {
+        let config = Config {
+            keys: ConfigKeys { keys },
+            data: data.into(),
+        };
+        Account {
+            lamports: 1,
+            data: borsh::to_vec(&config).unwrap(),
+            owner: crate::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn addresses(configs: &[DecodedAccount<Config>]) -> Vec<Address> {
+        configs.iter().map(|config| config.address).collect()
+    }
+
+    #[test]
+    fn test_find_configs() {
+        let key = address(1);
+        let other = address(2);
+        let source = InMemoryAccounts {
+            accounts: vec![
+                (address(10), config_account(vec![(key, true)], vec![7, 0])),
+                (
+                    address(11),
+                    config_account(vec![(other, true), (key, false)], vec![7, 0]),
+                ),
+                // The key only appears in the payload.
+                (
+                    address(12),
+                    config_account(vec![(other, false)], key.to_bytes().to_vec()),
+                ),
+                (address(13), config_account(vec![(other, true)], vec![8, 0])),
+                // Not a config account.
+                (
+                    address(14),
+                    Account {
+                        owner: address(99),
+                        ..config_account(vec![(key, true)], vec![])
+                    },
+                ),
+                // Uninitialized data that can't be decoded.
+                (
+                    address(15),
+                    Account {
+                        data: vec![],
+                        ..config_account(vec![], vec![])
+                    },
+                ),
+            ],
+        };
+
+        assert_eq!(
+            addresses(&find_configs_by_key(&source, &key, Some(4)).unwrap()),
+            vec![address(10), address(11)],
+        );
+        assert_eq!(
+            addresses(&find_configs_by_signer(&source, &key, Some(4)).unwrap()),
+            vec![address(10)],
+        );
+        assert_eq!(
+            addresses(&find_configs_by_key(&source, &key, None).unwrap()),
+            vec![address(10), address(11)],
+        );
+        // Positions past `max_keys` aren't searched.
+        assert_eq!(
+            addresses(&find_configs_by_key(&source, &key, Some(1)).unwrap()),
+            vec![address(10)],
+        );
+
+        assert_eq!(
+            addresses(&find_configs_by_payload(&source, 1, 0, &[7]).unwrap()),
+            vec![address(10)],
+        );
+        assert_eq!(
+            addresses(&find_configs_by_payload(&source, 2, 0, &[7, 0]).unwrap()),
+            vec![address(11)],
+        );
+        // Key counts that take more than one byte can't be filtered on.
+        assert!(matches!(
+            find_configs_by_payload(&source, 128, 0, &[7]),
+            Err(ScanError::TooManyKeys(128))
+        ));
+
+        let config_size = borsh::to_vec(&Config {
+            keys: ConfigKeys {
+                keys: vec![(key, true)],
+            },
+            data: vec![7, 0].into(),
+        })
+        .unwrap()
+        .len();
+        assert_eq!(
+            addresses(
+                &find_configs(&source, vec![RpcFilterType::DataSize(config_size as u64)]).unwrap()
+            ),
+            vec![address(10), address(13)],
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Signer accounts for `store` CPIs.
+
+use {
+    crate::generated::instructions::StoreCpi,
+    solana_account_info::AccountInfo,
+    solana_address::Address,
+    solana_program_error::{ProgramError, ProgramResult},
+};
+
+impl<'a, 'b> StoreCpi<'a, 'b> {
+    /// Picks the accounts for the signer keys of the new config from
+    /// `accounts`, in the order the program expects them.
+    ///
+    /// The program expects the account for the n-th signer key at position
+    /// n, counting the config account's own signer key too. That key is
+    /// given the config account as a placeholder, since the program checks
+    /// the config account itself for it. Every other signer key needs an
+    /// account in `accounts`.
+    ///
+    /// An account is flagged as a signer if it signed the calling
+    /// instruction, or if it is the program-derived address of one of
+    /// `signers_seeds` under `program_id`, the calling program. Accounts are
+    /// returned as `(AccountInfo, is_signer, is_writable)` remaining
+    /// accounts.
+    pub fn signer_accounts(
+        &self,
+        program_id: &Address,
+        signers_seeds: &[&[&[u8]]],
+        accounts: &'b [AccountInfo<'a>],
+    ) -> Result<Vec<(&'b AccountInfo<'a>, bool, bool)>, ProgramError> {
+        let pdas = signers_seeds
+            .iter()
+            .map(|seeds| {
+                Address::create_program_address(seeds, program_id)
+                    .map_err(|_| ProgramError::InvalidSeeds)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.__args
+            .keys
+            .keys
+            .iter()
+            .filter(|(_, is_signer)| *is_signer)
+            .map(|(key, _)| {
+                if key == self.config_account.0.key {
+                    return Ok((self.config_account.0, false, false));
+                }
+                let account = accounts
+                    .iter()
+                    .find(|account| account.key == key)
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                Ok((account, account.is_signer || pdas.contains(key), false))
+            })
+            .collect()
+    }
+
+    /// Invokes the instruction with the signer accounts picked by
+    /// [`Self::signer_accounts`], signing with `signers_seeds`.
+    ///
+    /// This lets a program-derived address act as a stored signer of a config
+    /// account: the program that owns the PDA signs for it with its seeds.
+    ///
+    /// ```ignore
+    /// StoreCpi::new(
+    ///     config_program,
+    ///     StoreCpiAccounts {
+    ///         config_account: (config_account, false),
+    ///     },
+    ///     StoreInstructionArgs {
+    ///         keys: ConfigKeys {
+    ///             keys: vec![(authority_pda, true)],
+    ///         },
+    ///         data: data.into(),
+    ///     },
+    /// )
+    /// .invoke_signed_with_signers(
+    ///     program_id,
+    ///     &[&[b"authority", &[authority_bump]]],
+    ///     accounts,
+    /// )?;
+    /// ```
+    pub fn invoke_signed_with_signers(
+        &self,
+        program_id: &Address,
+        signers_seeds: &[&[&[u8]]],
+        accounts: &'b [AccountInfo<'a>],
+    ) -> ProgramResult {
+        let signer_accounts = self.signer_accounts(program_id, signers_seeds, accounts)?;
+        self.invoke_signed_with_remaining_accounts(signers_seeds, &signer_accounts)
+    }
+}
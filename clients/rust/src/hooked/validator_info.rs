@@ -0,0 +1,436 @@
+//! Publishing and reading validator info.
+//!
+//! A validator info account lists [`VALIDATOR_INFO_ID`] as a non-signer key,
+//! followed by the validator identity as the only signer. Its payload is a
+//! JSON object with the validator's [`ValidatorDetails`], serialized as a
+//! bincode string: a `u64` length followed by the JSON bytes.
+//!
+//! The key layout and size limit are those of
+//! [`solana_config_interface::validator_info`], whose `ValidatorInfo` holds
+//! the payload as an unparsed JSON string.
+
+pub use solana_config_interface::validator_info::{
+    space as validator_info_space, ID as VALIDATOR_INFO_ID, MAX_VALIDATOR_INFO,
+};
+use {
+    crate::{accounts::Config, hooked::ConfigKeys, instructions::StoreBuilder},
+    serde::{Deserialize, Serialize},
+    solana_address::Address,
+    solana_config_interface::validator_info,
+    solana_instruction::{AccountMeta, Instruction},
+};
+
+/// The maximum length of the name, website, icon URL and Keybase username.
+pub const MAX_SHORT_FIELD_LENGTH: usize = 80;
+
+/// The maximum length of the details.
+pub const MAX_LONG_FIELD_LENGTH: usize = 300;
+
+/// The size of the payload's length prefix.
+const LENGTH_PREFIX_LEN: usize = 8;
+
+/// Errors returned when building or parsing validator info.
+#[derive(Debug, thiserror::Error)]
+pub enum ValidatorInfoError {
+    /// The name is empty.
+    #[error("Validator name is empty")]
+    EmptyName,
+    /// A field is longer than its limit.
+    #[error("{field} is longer than {max} bytes")]
+    FieldTooLong {
+        /// The field's JSON name.
+        field: &'static str,
+        /// The field's maximum length.
+        max: usize,
+    },
+    /// A URL field is not an `http` or `https` URL.
+    #[error("{0} is not an http(s) URL")]
+    InvalidUrl(&'static str),
+    /// The Keybase username has characters other than ASCII letters, digits
+    /// and underscores.
+    #[error("Invalid Keybase username")]
+    InvalidKeybaseUsername,
+    /// The serialized payload is larger than [`MAX_VALIDATOR_INFO`].
+    #[error("Validator info is {0} bytes, more than the maximum of {MAX_VALIDATOR_INFO}")]
+    InfoTooLarge(u64),
+    /// The account keys are not the validator info key and an identity.
+    #[error("Config keys are not validator info keys")]
+    InvalidKeys,
+    /// The account data is not a validator info account.
+    #[error("Invalid validator info account data")]
+    InvalidAccountData,
+    /// The payload is not a JSON object of validator info.
+    #[error("Invalid validator info JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+}
+
+/// A validator's published details, parsed from the JSON payload of a
+/// validator info account.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorDetails {
+    /// The validator's name.
+    #[serde(default)]
+    pub name: String,
+    /// The validator's website.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    /// A description of the validator.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// The URL of the validator's icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_url: Option<String>,
+    /// The validator's Keybase username.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keybase_username: Option<String>,
+}
+
+impl ValidatorDetails {
+    /// Creates validator details with just a name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Sets the website.
+    pub fn website(mut self, website: impl Into<String>) -> Self {
+        self.website = Some(website.into());
+        self
+    }
+
+    /// Sets the details.
+    pub fn details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+
+    /// Sets the icon URL.
+    pub fn icon_url(mut self, icon_url: impl Into<String>) -> Self {
+        self.icon_url = Some(icon_url.into());
+        self
+    }
+
+    /// Sets the Keybase username.
+    pub fn keybase_username(mut self, keybase_username: impl Into<String>) -> Self {
+        self.keybase_username = Some(keybase_username.into());
+        self
+    }
+
+    /// Checks the fields against their length and format limits, and the
+    /// serialized payload against [`MAX_VALIDATOR_INFO`].
+    pub fn check(&self) -> Result<(), ValidatorInfoError> {
+        if self.name.is_empty() {
+            return Err(ValidatorInfoError::EmptyName);
+        }
+        check_length("name", &self.name, MAX_SHORT_FIELD_LENGTH)?;
+        if let Some(website) = &self.website {
+            check_length("website", website, MAX_SHORT_FIELD_LENGTH)?;
+            check_url("website", website)?;
+        }
+        if let Some(details) = &self.details {
+            check_length("details", details, MAX_LONG_FIELD_LENGTH)?;
+        }
+        if let Some(icon_url) = &self.icon_url {
+            check_length("iconUrl", icon_url, MAX_SHORT_FIELD_LENGTH)?;
+            check_url("iconUrl", icon_url)?;
+        }
+        if let Some(keybase_username) = &self.keybase_username {
+            check_length("keybaseUsername", keybase_username, MAX_SHORT_FIELD_LENGTH)?;
+            if keybase_username.is_empty()
+                || !keybase_username
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+            {
+                return Err(ValidatorInfoError::InvalidKeybaseUsername);
+            }
+        }
+        check_size(self.to_json()?.len())
+    }
+
+    /// Serializes the fields as the JSON object stored in the account.
+    pub fn to_json(&self) -> Result<String, ValidatorInfoError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Checks the fields and serializes the `store` payload.
+    pub fn to_payload(&self) -> Result<Vec<u8>, ValidatorInfoError> {
+        self.check()?;
+        let json = self.to_json()?;
+        let mut payload = Vec::with_capacity(LENGTH_PREFIX_LEN + json.len());
+        payload.extend_from_slice(&(json.len() as u64).to_le_bytes());
+        payload.extend_from_slice(json.as_bytes());
+        Ok(payload)
+    }
+
+    /// Parses a validator info account's data into the validator identity
+    /// and its info.
+    ///
+    /// As with [`Self::from_config`], the field limits are not enforced.
+    pub fn from_account_data(data: &[u8]) -> Result<(Address, Self), ValidatorInfoError> {
+        let config =
+            Config::from_bytes(data).map_err(|_| ValidatorInfoError::InvalidAccountData)?;
+        Self::from_config(&config)
+    }
+
+    /// Parses a decoded config account into the validator identity and its
+    /// info.
+    ///
+    /// The payload must fit in [`MAX_VALIDATOR_INFO`], but the field limits
+    /// checked by [`Self::check`] are not enforced, since info published by
+    /// older tools may exceed them.
+    pub fn from_config(config: &Config) -> Result<(Address, Self), ValidatorInfoError> {
+        let identity =
+            *validator_info::identity(&config.keys.keys).ok_or(ValidatorInfoError::InvalidKeys)?;
+        let (len, json) = config
+            .data
+            .split_first_chunk::<LENGTH_PREFIX_LEN>()
+            .ok_or(ValidatorInfoError::InvalidAccountData)?;
+        let json = usize::try_from(u64::from_le_bytes(*len))
+            .ok()
+            .and_then(|len| json.get(..len))
+            .ok_or(ValidatorInfoError::InvalidAccountData)?;
+        check_size(json.len())?;
+        Ok((identity, serde_json::from_slice(json)?))
+    }
+}
+
+/// The keys of the validator info account for `identity`.
+pub fn validator_info_keys(identity: &Address) -> ConfigKeys {
+    ConfigKeys {
+        keys: validator_info::keys(identity),
+    }
+}
+
+/// Creates a validator info account and publishes `info` to it.
+///
+/// The account is sized with [`validator_info_space`], and `lamports` should
+/// be its rent-exempt minimum. The payer, the new account and the identity
+/// must sign.
+pub fn create_validator_info(
+    payer: &Address,
+    config_account: &Address,
+    identity: &Address,
+    info: &ValidatorDetails,
+    lamports: u64,
+) -> Result<Vec<Instruction>, ValidatorInfoError> {
+    Ok(vec![
+        solana_system_interface::instruction::create_account(
+            payer,
+            config_account,
+            lamports,
+            validator_info_space(),
+            &crate::ID,
+        ),
+        store_validator_info(config_account, true, identity, info)?,
+    ])
+}
+
+/// Publishes new `info` to an existing validator info account.
+///
+/// The identity must sign.
+pub fn update_validator_info(
+    config_account: &Address,
+    identity: &Address,
+    info: &ValidatorDetails,
+) -> Result<Instruction, ValidatorInfoError> {
+    store_validator_info(config_account, false, identity, info)
+}
+
+fn store_validator_info(
+    config_account: &Address,
+    is_config_signer: bool,
+    identity: &Address,
+    info: &ValidatorDetails,
+) -> Result<Instruction, ValidatorInfoError> {
+    Ok(StoreBuilder::new()
+        .config_account(*config_account, is_config_signer)
+        .keys(validator_info_keys(identity))
+        .data(info.to_payload()?.into())
+        .add_remaining_account(AccountMeta::new(*identity, true))
+        .instruction())
+}
+
+// Checks that a payload holding `json_len` bytes of JSON fits in
+// `MAX_VALIDATOR_INFO`.
+fn check_size(json_len: usize) -> Result<(), ValidatorInfoError> {
+    let len = (json_len as u64).saturating_add(LENGTH_PREFIX_LEN as u64);
+    if len > MAX_VALIDATOR_INFO {
+        return Err(ValidatorInfoError::InfoTooLarge(len));
+    }
+    Ok(())
+}
+
+fn check_length(field: &'static str, value: &str, max: usize) -> Result<(), ValidatorInfoError> {
+    if value.len() > max {
+        return Err(ValidatorInfoError::FieldTooLong { field, max });
+    }
+    Ok(())
+}
+
+fn check_url(field: &'static str, value: &str) -> Result<(), ValidatorInfoError> {
+    let rest = value
+        .strip_prefix("https://")
+        .or_else(|| value.strip_prefix("http://"))
+        .ok_or(ValidatorInfoError::InvalidUrl(field))?;
+    if rest.is_empty() || rest.chars().any(char::is_whitespace) {
+        return Err(ValidatorInfoError::InvalidUrl(field));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(seed: u8) -> Address {
+        Address::from([seed; 32])
+    }
+
+    fn account_data(instruction: &Instruction) -> Vec<u8> {
+        let mut data = instruction.data.clone();
+        data.resize(validator_info_space() as usize, 0);
+        data
+    }
+
+    #[test]
+    fn test_publish_and_parse() {
+        let payer = address(1);
+        let config_account = address(2);
+        let identity = address(3);
+        let info = ValidatorDetails::new("Validator")
+            .website("https://validator.example")
+            .details("A validator.")
+            .icon_url("https://validator.example/icon.png")
+            .keybase_username("validator_1");
+
+        let instructions =
+            create_validator_info(&payer, &config_account, &identity, &info, 1).unwrap();
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[1].accounts,
+            vec![
+                AccountMeta::new(config_account, true),
+                AccountMeta::new(identity, true),
+            ]
+        );
+        assert_eq!(
+            ValidatorDetails::from_account_data(&account_data(&instructions[1])).unwrap(),
+            (identity, info.clone()),
+        );
+
+        let instruction = update_validator_info(&config_account, &identity, &info).unwrap();
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(config_account, false),
+                AccountMeta::new(identity, true),
+            ]
+        );
+        assert_eq!(instruction.data, instructions[1].data);
+    }
+
+    #[test]
+    fn test_parse_existing() {
+        // As published by the Solana CLI.
+        let json = r#"{"name":"Validator","keybaseUsername":"validator","extra":1}"#;
+        let mut data = vec![2];
+        data.extend_from_slice(VALIDATOR_INFO_ID.as_ref());
+        data.push(0);
+        data.extend_from_slice(address(3).as_ref());
+        data.push(1);
+        data.extend_from_slice(&(json.len() as u64).to_le_bytes());
+        data.extend_from_slice(json.as_bytes());
+        data.resize(validator_info_space() as usize, 0);
+
+        assert_eq!(
+            ValidatorDetails::from_account_data(&data).unwrap(),
+            (
+                address(3),
+                ValidatorDetails::new("Validator").keybase_username("validator"),
+            ),
+        );
+
+        // Wrong keys.
+        data[33] = 1;
+        assert!(matches!(
+            ValidatorDetails::from_account_data(&data),
+            Err(ValidatorInfoError::InvalidKeys),
+        ));
+        data[33] = 0;
+
+        // Length past the end of the account.
+        data[67] = 0xFF;
+        data[68] = 0xFF;
+        assert!(matches!(
+            ValidatorDetails::from_account_data(&data),
+            Err(ValidatorInfoError::InvalidAccountData),
+        ));
+
+        // A payload larger than `MAX_VALIDATOR_INFO`, in an oversized account.
+        let json = format!(
+            r#"{{"name":"{}"}}"#,
+            "x".repeat(MAX_VALIDATOR_INFO as usize)
+        );
+        data.truncate(67);
+        data.extend_from_slice(&(json.len() as u64).to_le_bytes());
+        data.extend_from_slice(json.as_bytes());
+        assert!(matches!(
+            ValidatorDetails::from_account_data(&data),
+            Err(ValidatorInfoError::InfoTooLarge(_)),
+        ));
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(matches!(
+            ValidatorDetails::default().check(),
+            Err(ValidatorInfoError::EmptyName),
+        ));
+        assert!(matches!(
+            ValidatorDetails::new("x".repeat(81)).check(),
+            Err(ValidatorInfoError::FieldTooLong { field: "name", .. }),
+        ));
+        assert!(matches!(
+            ValidatorDetails::new("Validator")
+                .details("x".repeat(301))
+                .check(),
+            Err(ValidatorInfoError::FieldTooLong {
+                field: "details",
+                ..
+            }),
+        ));
+        assert!(matches!(
+            ValidatorDetails::new("Validator")
+                .website("validator.example")
+                .check(),
+            Err(ValidatorInfoError::InvalidUrl("website")),
+        ));
+        assert!(matches!(
+            ValidatorDetails::new("Validator")
+                .icon_url("https://")
+                .check(),
+            Err(ValidatorInfoError::InvalidUrl("iconUrl")),
+        ));
+        assert!(matches!(
+            ValidatorDetails::new("Validator")
+                .keybase_username("not valid")
+                .check(),
+            Err(ValidatorInfoError::InvalidKeybaseUsername),
+        ));
+
+        // Every field at its limit overflows the payload.
+        let long_url = format!("https://{}", "x".repeat(72));
+        let info = ValidatorDetails::new("x".repeat(80))
+            .website(long_url.clone())
+            .details("x".repeat(300))
+            .icon_url(long_url)
+            .keybase_username("x".repeat(80));
+        assert!(matches!(
+            info.check(),
+            Err(ValidatorInfoError::InfoTooLarge(_))
+        ));
+    }
+}
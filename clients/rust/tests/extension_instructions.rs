@@ -0,0 +1,96 @@
+//! The generated extension instruction builders encode the same
+//! instructions as the `solana-config-interface` helpers.
+
+use {
+    solana_address::Address,
+    solana_config_interface::instruction as interface,
+    solana_config_program_client::{
+        instructions::{
+            CloseBuilder, CompareAndStoreBuilder, FreezeBuilder, ResizeBuilder, SetKeysBuilder,
+            WriteBuilder,
+        },
+        ConfigKeys,
+    },
+    solana_hash::Hash,
+    solana_instruction::AccountMeta,
+};
+
+const CONFIG: Address = Address::new_from_array([1; 32]);
+const SIGNER: Address = Address::new_from_array([2; 32]);
+const OTHER: Address = Address::new_from_array([3; 32]);
+
+fn signer_metas() -> Vec<AccountMeta> {
+    vec![AccountMeta::new_readonly(SIGNER, true)]
+}
+
+#[test]
+fn test_close() {
+    let instruction = CloseBuilder::new()
+        .config_account(CONFIG, false)
+        .destination(OTHER)
+        .add_remaining_accounts(&signer_metas())
+        .instruction();
+    assert_eq!(instruction, interface::close(&CONFIG, &OTHER, &[SIGNER]));
+}
+
+#[test]
+fn test_resize() {
+    let instruction = ResizeBuilder::new()
+        .config_account(CONFIG, false)
+        .funding_account(OTHER, true)
+        .new_len(1_000)
+        .add_remaining_accounts(&signer_metas())
+        .instruction();
+    assert_eq!(
+        instruction,
+        interface::resize(&CONFIG, &OTHER, 1_000, &[SIGNER])
+    );
+}
+
+#[test]
+fn test_write() {
+    let instruction = WriteBuilder::new()
+        .config_account(CONFIG, false)
+        .offset(7)
+        .data(vec![1, 2, 3].into())
+        .add_remaining_accounts(&signer_metas())
+        .instruction();
+    assert_eq!(
+        instruction,
+        interface::write(&CONFIG, 7, vec![1, 2, 3], &[SIGNER])
+    );
+}
+
+#[test]
+fn test_compare_and_store() {
+    let store = interface::store(&CONFIG, true, vec![(OTHER, false)], &[1u8, 2, 3]);
+    let expected_hash = Hash::new_from_array([4; 32]);
+    let instruction = CompareAndStoreBuilder::new()
+        .config_account(CONFIG, true)
+        .expected_hash(expected_hash.to_bytes())
+        .input(store.data.clone().into())
+        .instruction();
+    assert_eq!(
+        instruction,
+        interface::compare_and_store(store, &expected_hash)
+    );
+}
+
+#[test]
+fn test_set_keys() {
+    let keys = vec![(SIGNER, true), (OTHER, false)];
+    let instruction = SetKeysBuilder::new()
+        .config_account(CONFIG, true)
+        .keys(ConfigKeys { keys: keys.clone() })
+        .instruction();
+    assert_eq!(instruction, interface::set_keys(&CONFIG, keys, &[]));
+}
+
+#[test]
+fn test_freeze() {
+    let instruction = FreezeBuilder::new()
+        .config_account(CONFIG, false)
+        .add_remaining_accounts(&signer_metas())
+        .instruction();
+    assert_eq!(instruction, interface::freeze(&CONFIG, &[SIGNER]));
+}
@@ -0,0 +1,223 @@
+//! `store` CPIs signed by a program-derived address, executed against the
+//! regression program in `program/fuzz/program-mb.so`.
+//!
+//! A builtin stands in for the calling program. It invokes the instruction
+//! that `StoreCpi` would, with the remaining accounts picked by
+//! `StoreCpi::signer_accounts`.
+
+use {
+    mollusk_svm::{
+        program::{loader_keys::LOADER_V3, Builtin},
+        result::Check,
+        Mollusk,
+    },
+    solana_account::Account,
+    solana_account_info::AccountInfo,
+    solana_address::Address,
+    solana_config_program_client::{
+        instructions::{Store, StoreCpi, StoreCpiAccounts, StoreInstructionArgs},
+        ConfigKeys, ID,
+    },
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_program_runtime::declare_process_instruction,
+    std::cell::RefCell,
+};
+
+const CALLER_ID: Address = Address::new_from_array([7; 32]);
+
+thread_local! {
+    // The instruction for the caller to invoke, and the addresses it signs
+    // for.
+    static CPI: RefCell<Option<(Instruction, Vec<Address>)>> = const { RefCell::new(None) };
+}
+
+declare_process_instruction!(Caller, 0, |invoke_context| {
+    let (instruction, signers) = CPI.with(|cpi| cpi.borrow_mut().take()).unwrap();
+    invoke_context.native_invoke(instruction, &signers)
+});
+
+fn setup() -> Mollusk {
+    let elf = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../program/fuzz/program-mb.so"
+    ))
+    .expect("failed to read program/fuzz/program-mb.so");
+    let mut mollusk = Mollusk::default();
+    mollusk.add_program_with_loader_and_elf(&ID, &LOADER_V3, &elf);
+    mollusk.program_cache.add_builtin(Builtin {
+        program_id: CALLER_ID,
+        name: "store_cpi_caller",
+        entrypoint: Caller::vm,
+    });
+    mollusk
+}
+
+// Run a `store` of `keys` through the caller, with `config` signing the
+// outer instruction and the caller signing with `signers_seeds`.
+fn store_via_cpi(
+    mollusk: &Mollusk,
+    config: &Address,
+    config_account: &Account,
+    keys: Vec<(Address, bool)>,
+    accounts: &[Address],
+    signers_seeds: &[&[&[u8]]],
+    checks: &[Check],
+) -> Result<(), ProgramError> {
+    let args = StoreInstructionArgs {
+        keys: ConfigKeys { keys },
+        data: vec![1, 2, 3].into(),
+    };
+
+    // The remaining accounts, as the caller would pick them.
+    let mut lamports = [0; 4];
+    let mut data = [[0u8; 0]; 4];
+    let mut slots = lamports.iter_mut().zip(data.iter_mut());
+    let (program_lamports, program_data) = slots.next().unwrap();
+    let program_info =
+        AccountInfo::new(&ID, false, false, program_lamports, program_data, &ID, true);
+    let (config_lamports, config_data) = slots.next().unwrap();
+    let config_info =
+        AccountInfo::new(config, true, true, config_lamports, config_data, &ID, false);
+    let account_infos = accounts
+        .iter()
+        .zip(slots)
+        .map(|(address, (lamports, data))| {
+            AccountInfo::new(address, false, false, lamports, data, &ID, false)
+        })
+        .collect::<Vec<_>>();
+    let cpi = StoreCpi::new(
+        &program_info,
+        StoreCpiAccounts {
+            config_account: (&config_info, true),
+        },
+        args.clone(),
+    );
+    let remaining_accounts = cpi
+        .signer_accounts(&CALLER_ID, signers_seeds, &account_infos)?
+        .into_iter()
+        .map(|(account, is_signer, is_writable)| AccountMeta {
+            pubkey: *account.key,
+            is_signer,
+            is_writable,
+        })
+        .collect::<Vec<_>>();
+    let instruction = Store {
+        config_account: (*config, true),
+    }
+    .instruction_with_remaining_accounts(args, &remaining_accounts);
+    let signers = signers_seeds
+        .iter()
+        .map(|seeds| Address::create_program_address(seeds, &CALLER_ID).unwrap())
+        .collect();
+    CPI.with(|cpi| *cpi.borrow_mut() = Some((instruction, signers)));
+
+    let mut metas = vec![AccountMeta::new(*config, true)];
+    metas.extend(
+        accounts
+            .iter()
+            .map(|address| AccountMeta::new_readonly(*address, false)),
+    );
+    metas.push(AccountMeta::new_readonly(ID, false));
+    let mut mollusk_accounts = vec![(*config, config_account.clone())];
+    mollusk_accounts.extend(
+        accounts
+            .iter()
+            .map(|address| (*address, Account::default())),
+    );
+    mollusk_accounts.push((
+        ID,
+        Account {
+            owner: LOADER_V3,
+            executable: true,
+            ..Account::default()
+        },
+    ));
+    mollusk.process_and_validate_instruction(
+        &Instruction::new_with_bytes(CALLER_ID, &[], metas),
+        &mollusk_accounts,
+        checks,
+    );
+    Ok(())
+}
+
+#[test]
+fn test_store_cpi_signed_by_pda() {
+    let mollusk = setup();
+    let (pda, bump) = Address::find_program_address(&[b"authority"], &CALLER_ID);
+    let seeds: &[&[&[u8]]] = &[&[b"authority", &[bump]]];
+    let config = Address::new_from_array([2; 32]);
+    let other = Address::new_from_array([3; 32]);
+
+    // The config account's own signer key comes first, so the program looks
+    // for the PDA's account after a placeholder for it.
+    let keys = vec![(config, true), (pda, true), (other, false)];
+    let mut data = borsh::to_vec(&ConfigKeys { keys: keys.clone() }).unwrap();
+    data.resize(128, 0);
+    let config_account = Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+        data,
+        owner: ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let mut stored = borsh::to_vec(&ConfigKeys { keys: keys.clone() }).unwrap();
+    stored.extend([1, 2, 3]);
+    let mut expected_data = stored.clone();
+    expected_data.resize(128, 0);
+
+    store_via_cpi(
+        &mollusk,
+        &config,
+        &config_account,
+        keys.clone(),
+        &[other, pda],
+        seeds,
+        &[
+            Check::success(),
+            Check::account(&config).data(&expected_data).build(),
+        ],
+    )
+    .unwrap();
+
+    // Without the seeds, the PDA doesn't sign.
+    store_via_cpi(
+        &mollusk,
+        &config,
+        &config_account,
+        keys.clone(),
+        &[other, pda],
+        &[],
+        &[Check::err(ProgramError::MissingRequiredSignature)],
+    )
+    .unwrap();
+
+    // Every signer key other than the config account needs an account.
+    assert_eq!(
+        store_via_cpi(
+            &mollusk,
+            &config,
+            &config_account,
+            keys,
+            &[other],
+            seeds,
+            &[]
+        ),
+        Err(ProgramError::NotEnoughAccountKeys),
+    );
+
+    // Seeds that don't derive a valid PDA.
+    let bad_seeds: &[&[&[u8]]] = &[&[&[0u8; 33]]];
+    assert_eq!(
+        store_via_cpi(
+            &mollusk,
+            &config,
+            &config_account,
+            vec![(pda, true)],
+            &[pda],
+            bad_seeds,
+            &[]
+        ),
+        Err(ProgramError::InvalidSeeds),
+    );
+}
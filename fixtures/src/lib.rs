@@ -0,0 +1,576 @@
+//! Record and replay Mollusk fixtures for the config program.
+//!
+//! A fixture captures one instruction run under Mollusk: its accounts,
+//! instruction, compute budget, feature set and sysvars, and the result. It is
+//! written in the formats of `program/fuzz`, as a protobuf blob (`.fix`) or as
+//! JSON (`.json`), named `instr-<hash>` after the base58 Keccak hash of its
+//! contents.
+//!
+//! Replaying runs the input of each fixture against a program binary, and
+//! reports every way the result differs from the recorded one.
+
+pub use mollusk_svm_fuzz_fixture::Fixture;
+use {
+    mollusk_svm::{program::loader_keys::LOADER_V3, result::InstructionResult, Mollusk},
+    mollusk_svm_fuzz_fixture::{effects::Effects, proto::InstrFixture as ProtoFixture},
+    mollusk_svm_fuzz_fs::SerializableFixture,
+    prost::Message,
+    solana_account::Account,
+    solana_instruction::{error::InstructionError, Instruction},
+    solana_pubkey::Pubkey,
+    std::{
+        fmt, fs, io,
+        path::{Path, PathBuf},
+    },
+    thiserror::Error,
+};
+
+/// Errors returned when reading or writing fixtures.
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    /// A fixture file or directory could not be read or written.
+    #[error("{}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    /// A `.fix` file is not a valid protobuf fixture.
+    #[error("{}: invalid fixture blob: {source}", path.display())]
+    Decode {
+        path: PathBuf,
+        source: prost::DecodeError,
+    },
+    /// A `.json` file is not a valid JSON fixture.
+    #[error("{}: invalid JSON fixture: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// The fixture has no input or no output.
+    #[error("{}: fixture is missing its input or output", path.display())]
+    Incomplete { path: PathBuf },
+    /// The file extension is neither `.fix` nor `.json`.
+    #[error("{}: not a fixture file", path.display())]
+    UnknownFormat { path: PathBuf },
+}
+
+/// The file format of a fixture.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// A protobuf blob, with the `.fix` extension.
+    Blob,
+    /// JSON, with the `.json` extension.
+    Json,
+}
+
+impl Format {
+    /// The file extension, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Blob => "fix",
+            Self::Json => "json",
+        }
+    }
+
+    /// The format of a fixture file, from its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "fix" => Some(Self::Blob),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Run `instruction` under `mollusk`, and capture the run as a fixture.
+pub fn record(
+    mollusk: &Mollusk,
+    instruction: &Instruction,
+    accounts: &[(Pubkey, Account)],
+) -> (InstructionResult, Fixture) {
+    let result = mollusk.process_instruction(instruction, accounts);
+    let mut fixture = mollusk_svm::fuzz::mollusk::build_fixture_from_mollusk_test(
+        mollusk,
+        instruction,
+        accounts,
+        &result,
+    );
+    // The execution time varies between runs, and would change the name of
+    // the fixture every time it's recorded.
+    fixture.output.execution_time = 0;
+    (result, fixture)
+}
+
+/// Run the input of `fixture` under `mollusk`, and capture the run as a new
+/// fixture with the same input.
+///
+/// The compute budget, feature set and sysvars of `mollusk` are replaced by
+/// those of the fixture.
+pub fn rerecord(mollusk: &mut Mollusk, fixture: &Fixture) -> Fixture {
+    let result = mollusk.process_fixture(fixture);
+    let mut output = Effects::from(&result);
+    output.execution_time = 0;
+    Fixture {
+        input: fixture.input.clone(),
+        output,
+    }
+}
+
+/// The file name of `fixture` in `format`: `instr-<hash>.<extension>`.
+pub fn file_name(fixture: &Fixture, format: Format) -> String {
+    let hash = ProtoFixture::from(fixture.clone()).hash();
+    format!("instr-{hash}.{}", format.extension())
+}
+
+/// Write `fixture` to `dir` in `format`, creating the directory if needed,
+/// and return the path of the new file.
+pub fn write_fixture(
+    fixture: &Fixture,
+    dir: &Path,
+    format: Format,
+) -> Result<PathBuf, FixtureError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| FixtureError::Io { path, source }
+    };
+
+    let proto = ProtoFixture::from(fixture.clone());
+    let contents = match format {
+        Format::Blob => proto.encode_to_vec(),
+        Format::Json => serde_json::to_vec_pretty(&proto).map_err(|source| FixtureError::Json {
+            path: dir.to_path_buf(),
+            source,
+        })?,
+    };
+    fs::create_dir_all(dir).map_err(io_error(dir))?;
+    let path = dir.join(file_name(fixture, format));
+    fs::write(&path, contents).map_err(io_error(&path))?;
+    Ok(path)
+}
+
+/// Load a fixture from a `.fix` or `.json` file.
+pub fn load_fixture(path: &Path) -> Result<Fixture, FixtureError> {
+    let format = Format::from_path(path).ok_or_else(|| FixtureError::UnknownFormat {
+        path: path.to_path_buf(),
+    })?;
+    let contents = fs::read(path).map_err(|source| FixtureError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let proto = match format {
+        Format::Blob => {
+            <ProtoFixture as Message>::decode(contents.as_slice()).map_err(|source| {
+                FixtureError::Decode {
+                    path: path.to_path_buf(),
+                    source,
+                }
+            })?
+        }
+        Format::Json => serde_json::from_slice::<ProtoFixture>(&contents).map_err(|source| {
+            FixtureError::Json {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?,
+    };
+    // Converting a fixture without an input or output panics.
+    if proto.input.is_none() || proto.output.is_none() {
+        return Err(FixtureError::Incomplete {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(proto.into())
+}
+
+/// Load every `.fix` and `.json` fixture in `dir`, sorted by path. Other
+/// files are skipped.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<(PathBuf, Fixture)>, FixtureError> {
+    let io_error = |source| FixtureError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut paths = fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+    paths.retain(|path| path.is_file() && Format::from_path(path).is_some());
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| load_fixture(&path).map(|fixture| (path, fixture)))
+        .collect()
+}
+
+/// A Mollusk instance running the program binary `elf` at `program_id`,
+/// under the upgradeable loader.
+pub fn mollusk_with_elf(program_id: &Pubkey, elf: &[u8]) -> Mollusk {
+    let mut mollusk = Mollusk::default();
+    mollusk.add_program_with_loader_and_elf(program_id, &LOADER_V3, elf);
+    mollusk
+}
+
+/// A difference between the recorded result of a fixture and the result of
+/// replaying it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diff {
+    /// The program result, as encoded in fixtures, with `0` for success.
+    ProgramResult { expected: u64, actual: u64 },
+    /// The compute units consumed.
+    ComputeUnits { expected: u64, actual: u64 },
+    /// The return data.
+    ReturnData { expected: Vec<u8>, actual: Vec<u8> },
+    /// A resulting account recorded in the fixture is missing.
+    MissingAccount { pubkey: Pubkey },
+    /// A resulting account not recorded in the fixture.
+    UnexpectedAccount { pubkey: Pubkey },
+    /// The lamports of a resulting account.
+    Lamports {
+        pubkey: Pubkey,
+        expected: u64,
+        actual: u64,
+    },
+    /// The data of a resulting account.
+    Data {
+        pubkey: Pubkey,
+        expected: Vec<u8>,
+        actual: Vec<u8>,
+    },
+    /// The owner of a resulting account.
+    Owner {
+        pubkey: Pubkey,
+        expected: Pubkey,
+        actual: Pubkey,
+    },
+    /// Whether a resulting account is executable.
+    Executable {
+        pubkey: Pubkey,
+        expected: bool,
+        actual: bool,
+    },
+}
+
+struct DisplayProgramResult(u64);
+
+impl fmt::Display for DisplayProgramResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => f.write_str("success"),
+            code => write!(f, "{:?}", InstructionError::from(code)),
+        }
+    }
+}
+
+// The first byte at which `expected` and `actual` differ.
+fn first_difference(expected: &[u8], actual: &[u8]) -> usize {
+    expected
+        .iter()
+        .zip(actual)
+        .position(|(expected, actual)| expected != actual)
+        .unwrap_or_else(|| expected.len().min(actual.len()))
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProgramResult { expected, actual } => write!(
+                f,
+                "program result: expected {}, got {}",
+                DisplayProgramResult(*expected),
+                DisplayProgramResult(*actual)
+            ),
+            Self::ComputeUnits { expected, actual } => {
+                write!(f, "compute units: expected {expected}, got {actual}")
+            }
+            Self::ReturnData { expected, actual } => {
+                write!(f, "return data: expected {expected:?}, got {actual:?}")
+            }
+            Self::MissingAccount { pubkey } => write!(f, "account {pubkey}: missing"),
+            Self::UnexpectedAccount { pubkey } => write!(f, "account {pubkey}: unexpected"),
+            Self::Lamports {
+                pubkey,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "account {pubkey}: expected {expected} lamports, got {actual}"
+            ),
+            Self::Data {
+                pubkey,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "account {pubkey}: data differs from byte {} (expected {} bytes, got {})",
+                first_difference(expected, actual),
+                expected.len(),
+                actual.len()
+            ),
+            Self::Owner {
+                pubkey,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "account {pubkey}: expected owner {expected}, got {actual}"
+            ),
+            Self::Executable {
+                pubkey,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "account {pubkey}: expected executable {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+fn diff_accounts(expected: &[(Pubkey, Account)], actual: &[(Pubkey, Account)]) -> Vec<Diff> {
+    let mut diffs = vec![];
+    for (pubkey, expected) in expected {
+        let Some((_, actual)) = actual.iter().find(|(key, _)| key == pubkey) else {
+            diffs.push(Diff::MissingAccount { pubkey: *pubkey });
+            continue;
+        };
+        let pubkey = *pubkey;
+        if expected.lamports != actual.lamports {
+            diffs.push(Diff::Lamports {
+                pubkey,
+                expected: expected.lamports,
+                actual: actual.lamports,
+            });
+        }
+        if expected.data != actual.data {
+            diffs.push(Diff::Data {
+                pubkey,
+                expected: expected.data.clone(),
+                actual: actual.data.clone(),
+            });
+        }
+        if expected.owner != actual.owner {
+            diffs.push(Diff::Owner {
+                pubkey,
+                expected: expected.owner,
+                actual: actual.owner,
+            });
+        }
+        if expected.executable != actual.executable {
+            diffs.push(Diff::Executable {
+                pubkey,
+                expected: expected.executable,
+                actual: actual.executable,
+            });
+        }
+    }
+    diffs.extend(
+        actual
+            .iter()
+            .filter(|(pubkey, _)| !expected.iter().any(|(key, _)| key == pubkey))
+            .map(|(pubkey, _)| Diff::UnexpectedAccount { pubkey: *pubkey }),
+    );
+    diffs
+}
+
+/// Replay `fixture` under `mollusk`, and return how the result differs from
+/// the recorded one.
+///
+/// The compute budget, feature set and sysvars of `mollusk` are replaced by
+/// those of the fixture. The execution time is never compared.
+pub fn replay(mollusk: &mut Mollusk, fixture: &Fixture, ignore_compute_units: bool) -> Vec<Diff> {
+    let expected = &fixture.output;
+    let actual = Effects::from(&mollusk.process_fixture(fixture));
+
+    let mut diffs = vec![];
+    if expected.program_result != actual.program_result {
+        diffs.push(Diff::ProgramResult {
+            expected: expected.program_result,
+            actual: actual.program_result,
+        });
+    }
+    if !ignore_compute_units && expected.compute_units_consumed != actual.compute_units_consumed {
+        diffs.push(Diff::ComputeUnits {
+            expected: expected.compute_units_consumed,
+            actual: actual.compute_units_consumed,
+        });
+    }
+    if expected.return_data != actual.return_data {
+        diffs.push(Diff::ReturnData {
+            expected: expected.return_data.clone(),
+            actual: actual.return_data,
+        });
+    }
+    diffs.extend(diff_accounts(
+        &expected.resulting_accounts,
+        &actual.resulting_accounts,
+    ));
+    diffs
+}
+
+/// Replay every fixture in `dir` under `mollusk`, returning the differences
+/// found for each fixture, by path.
+pub fn replay_dir(
+    mollusk: &mut Mollusk,
+    dir: &Path,
+    ignore_compute_units: bool,
+) -> Result<Vec<(PathBuf, Vec<Diff>)>, FixtureError> {
+    Ok(load_fixtures(dir)?
+        .into_iter()
+        .map(|(path, fixture)| {
+            let diffs = replay(mollusk, &fixture, ignore_compute_units);
+            (path, diffs)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_config_interface::{instruction::store, state::ConfigKeys},
+        solana_program_error::ProgramError,
+    };
+
+    fn fuzz_dir(format: Format) -> PathBuf {
+        let dir = match format {
+            Format::Blob => "blob",
+            Format::Json => "json",
+        };
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../program/fuzz")
+            .join(dir)
+    }
+
+    fn regression_mollusk() -> Mollusk {
+        let elf =
+            fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("../program/fuzz/program-mb.so"))
+                .unwrap();
+        mollusk_with_elf(&solana_config_interface::id(), &elf)
+    }
+
+    #[test]
+    fn test_load_program_fixtures() {
+        let blobs = load_fixtures(&fuzz_dir(Format::Blob)).unwrap();
+        let json = load_fixtures(&fuzz_dir(Format::Json)).unwrap();
+        assert!(!blobs.is_empty());
+        assert_eq!(blobs.len(), json.len());
+
+        // Both directories hold the same fixtures. The blobs were recorded
+        // before a compute budget field was dropped from the protobuf layout,
+        // so their compute budgets no longer decode, and their names no
+        // longer match their hashes.
+        for ((blob_path, blob), (json_path, json)) in blobs.iter().zip(&json) {
+            assert_eq!(blob_path.file_stem(), json_path.file_stem());
+            assert_eq!(blob.input.program_id, json.input.program_id);
+            assert_eq!(
+                blob.input.instruction_accounts,
+                json.input.instruction_accounts
+            );
+            assert_eq!(blob.input.instruction_data, json.input.instruction_data);
+            assert_eq!(blob.input.accounts, json.input.accounts);
+            assert_eq!(blob.output, json.output);
+        }
+    }
+
+    #[test]
+    fn test_replay_program_fixtures() {
+        let mut mollusk = regression_mollusk();
+        for (path, diffs) in replay_dir(&mut mollusk, &fuzz_dir(Format::Json), true).unwrap() {
+            assert_eq!(diffs, vec![], "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut mollusk = regression_mollusk();
+        let config = Pubkey::new_unique();
+        let keys = vec![(Pubkey::new_unique(), false)];
+        let space = bincode::serialized_size(&(ConfigKeys { keys: keys.clone() }, 42u64)).unwrap();
+        let accounts = vec![(
+            config,
+            Account::new(
+                mollusk.sysvars.rent.minimum_balance(space as usize),
+                space as usize,
+                &solana_config_interface::id(),
+            ),
+        )];
+        let instruction = store(&config, true, keys, &42u64);
+
+        let (result, fixture) = record(&mollusk, &instruction, &accounts);
+        assert!(result.program_result.is_ok());
+        assert_eq!(fixture.input.instruction_data, instruction.data);
+
+        let dir = tempfile::tempdir().unwrap();
+        for format in [Format::Blob, Format::Json] {
+            let path = write_fixture(&fixture, dir.path(), format).unwrap();
+            assert_eq!(path, dir.path().join(file_name(&fixture, format)));
+            assert_eq!(load_fixture(&path).unwrap(), fixture);
+        }
+        // Recording again gives the same fixture.
+        assert_eq!(record(&mollusk, &instruction, &accounts).1, fixture);
+
+        let diffs = replay_dir(&mut mollusk, dir.path(), false).unwrap();
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs.iter().all(|(_, diffs)| diffs.is_empty()));
+
+        // Tampered effects are reported.
+        let mut tampered = fixture.clone();
+        tampered.output.program_result = u64::from(ProgramError::InvalidArgument);
+        tampered.output.compute_units_consumed += 1;
+        *tampered.output.resulting_accounts[0]
+            .1
+            .data
+            .last_mut()
+            .unwrap() ^= 1;
+        tampered.output.resulting_accounts[0].1.lamports = 0;
+        let pubkey = config;
+        assert_eq!(
+            replay(&mut mollusk, &tampered, false),
+            vec![
+                Diff::ProgramResult {
+                    expected: u64::from(ProgramError::InvalidArgument),
+                    actual: 0,
+                },
+                Diff::ComputeUnits {
+                    expected: fixture.output.compute_units_consumed + 1,
+                    actual: fixture.output.compute_units_consumed,
+                },
+                Diff::Lamports {
+                    pubkey,
+                    expected: 0,
+                    actual: accounts[0].1.lamports,
+                },
+                Diff::Data {
+                    pubkey,
+                    expected: tampered.output.resulting_accounts[0].1.data.clone(),
+                    actual: fixture.output.resulting_accounts[0].1.data.clone(),
+                },
+            ]
+        );
+        assert_eq!(replay(&mut mollusk, &tampered, true).len(), 3);
+    }
+
+    #[test]
+    fn test_load_invalid_fixtures() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("instr-invalid.fix");
+        fs::write(&path, [0xFF; 8]).unwrap();
+        assert!(matches!(
+            load_fixture(&path),
+            Err(FixtureError::Decode { .. })
+        ));
+
+        let path = dir.path().join("instr-empty.json");
+        fs::write(&path, "{}").unwrap();
+        assert!(matches!(
+            load_fixture(&path),
+            Err(FixtureError::Incomplete { .. })
+        ));
+
+        assert!(matches!(
+            load_fixture(Path::new("fixture.txt")),
+            Err(FixtureError::UnknownFormat { .. })
+        ));
+        assert!(matches!(
+            load_fixture(&dir.path().join("instr-missing.fix")),
+            Err(FixtureError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,113 @@
+//! Record and replay config program fixtures against a program binary.
+
+use {
+    clap::{Parser, Subcommand},
+    solana_config_fixtures::{
+        load_fixtures, mollusk_with_elf, replay_dir, rerecord, write_fixture, FixtureError, Format,
+    },
+    solana_pubkey::Pubkey,
+    std::{fs, path::PathBuf, process::ExitCode},
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Replay a directory of fixtures against a program, and report where
+    /// the results differ from the recorded ones.
+    Replay {
+        /// The program binary.
+        program: PathBuf,
+        /// The directory of `.fix` and `.json` fixtures.
+        fixtures: PathBuf,
+        /// The address of the program.
+        #[arg(long, default_value_t = solana_config_interface::id())]
+        program_id: Pubkey,
+        /// Don't compare compute units.
+        #[arg(long)]
+        ignore_compute_units: bool,
+    },
+    /// Run the inputs of a directory of fixtures against a program, and
+    /// record the results as new fixtures, such as to pin the behavior of a
+    /// release before an upgrade.
+    Record {
+        /// The program binary.
+        program: PathBuf,
+        /// The directory of `.fix` and `.json` fixtures to take inputs from.
+        fixtures: PathBuf,
+        /// The directory to write the new fixtures to.
+        output: PathBuf,
+        /// The address of the program.
+        #[arg(long, default_value_t = solana_config_interface::id())]
+        program_id: Pubkey,
+        /// Write JSON fixtures, rather than protobuf blobs.
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn read_program(path: &PathBuf) -> Result<Vec<u8>, FixtureError> {
+    fs::read(path).map_err(|source| FixtureError::Io {
+        path: path.clone(),
+        source,
+    })
+}
+
+fn run(command: Command) -> Result<bool, FixtureError> {
+    match command {
+        Command::Replay {
+            program,
+            fixtures,
+            program_id,
+            ignore_compute_units,
+        } => {
+            let mut mollusk = mollusk_with_elf(&program_id, &read_program(&program)?);
+            let results = replay_dir(&mut mollusk, &fixtures, ignore_compute_units)?;
+            let mut failed = 0usize;
+            for (path, diffs) in &results {
+                if diffs.is_empty() {
+                    println!("ok   {}", path.display());
+                    continue;
+                }
+                failed = failed.saturating_add(1);
+                println!("FAIL {}", path.display());
+                for diff in diffs {
+                    println!("     {diff}");
+                }
+            }
+            println!("{} fixtures, {failed} failed", results.len());
+            Ok(failed == 0)
+        }
+        Command::Record {
+            program,
+            fixtures,
+            output,
+            program_id,
+            json,
+        } => {
+            let mut mollusk = mollusk_with_elf(&program_id, &read_program(&program)?);
+            let format = if json { Format::Json } else { Format::Blob };
+            for (path, fixture) in load_fixtures(&fixtures)? {
+                let new_path = write_fixture(&rerecord(&mut mollusk, &fixture), &output, format)?;
+                println!("{} -> {}", path.display(), new_path.display());
+            }
+            Ok(true)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,12 +1,147 @@
-//! Program instruction helpers.
+//! Program instructions and instruction helpers.
 
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use {crate::state::ConfigKeys, alloc::vec::Vec, solana_hash::Hash};
+#[cfg(feature = "bincode")]
 use {
-    crate::{id, state::ConfigKeys},
-    bincode::serialized_size,
+    crate::{
+        id,
+        space::ConfigSpace,
+        state::ConfigKeysRef,
+        validate::{
+            check_signers_in_order, check_stored_signers, check_threshold, check_unique_keys,
+            parse_store_keys, StoreError,
+        },
+    },
     solana_instruction::{AccountMeta, Instruction},
     solana_pubkey::Pubkey,
 };
 
+/// The first byte of the instruction data for every instruction other than
+/// `store`.
+///
+/// `store` instruction data begins with the `ShortU16` length of its keys,
+/// which the program caps well below this value, so the two encodings can
+/// never be confused.
+pub const EXTENSION_PREFIX: u8 = 0xFF;
+
+/// The maximum number of keys the program accepts in `store` instruction
+/// data.
+///
+/// This is the maximum transaction packet size of 1232 bytes, less up to 3
+/// bytes for the `ShortU16` length, divided by the size of a
+/// `(Pubkey, bool)` entry.
+pub const MAX_STORE_KEYS: usize = (1232 - 3) / (32 + 1);
+
+/// Config program instructions, other than `store`.
+///
+/// Instruction data is [`EXTENSION_PREFIX`] followed by the bincode-encoded
+/// instruction.
+///
+/// Signers are the signer keys currently stored in the config account's
+/// `ConfigKeys`, or the config account itself if no signers are stored. A
+/// stored signer equal to the config account is satisfied by the config
+/// account signing, rather than by a separate account. If the stored keys
+/// record a signer threshold, only that many of the stored signers need sign.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum ConfigInstruction {
+    /// Close a config account, zeroing its data and transferring all of
+    /// its lamports to a destination account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The config account.
+    ///   1. `[writable]` The destination account.
+    ///   2. ..2+N `[signer]` The N stored signers.
+    Close,
+
+    /// Resize a config account's data, preserving its keys and as much of
+    /// its data as fits in the new length.
+    ///
+    /// When growing, the funding account pays any lamports needed to keep
+    /// the config account rent-exempt. When shrinking, the difference
+    /// between the rent-exempt minimums for the old and new lengths is
+    /// refunded to the funding account. Any other lamports stay in the
+    /// config account.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The config account.
+    ///   1. `[writable, signer]` The funding account. Only required to sign
+    ///      when lamports must be added.
+    ///   2. `[]` The system program.
+    ///   3. ..3+N `[signer]` The N stored signers.
+    Resize {
+        /// The new length of the account data, which can't be smaller than
+        /// the stored `ConfigKeys`.
+        new_len: u64,
+    },
+
+    /// Write bytes into a config account's data at an offset, leaving the
+    /// keys and the rest of the data untouched.
+    ///
+    /// Allows populating data too large to fit in a single `store`
+    /// instruction over several transactions.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The config account.
+    ///   1. ..1+N `[signer]` The N stored signers.
+    Write {
+        /// The offset to write at, relative to the end of the stored
+        /// `ConfigKeys`.
+        offset: u32,
+        /// The bytes to write.
+        data: Vec<u8>,
+    },
+
+    /// Store new keys and data, exactly as `store` does, but only if the
+    /// config account's current data still hashes to an expected value.
+    ///
+    /// The hash covers the account data following the stored `ConfigKeys`,
+    /// through the end of the account. See `ConfigKeysRef::data_hash`.
+    ///
+    /// Accounts expected by this instruction are the same as for `store`.
+    CompareAndStore {
+        /// The SHA-256 hash of the current config data.
+        expected_hash: Hash,
+        /// The `store` instruction data.
+        input: Vec<u8>,
+    },
+
+    /// Replace a config account's keys, moving its data to follow the new
+    /// keys but otherwise leaving it untouched.
+    ///
+    /// Unlike `store`, new signer keys need not already be stored signers.
+    /// Like `store`, the new keys are limited to [`MAX_STORE_KEYS`].
+    /// The account isn't resized, so if the new keys are longer, the bytes
+    /// pushed off the end of the account must be zero.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The config account.
+    ///   1. ..1+N `[signer]` The N stored signers.
+    SetKeys {
+        /// The new keys.
+        keys: ConfigKeys,
+    },
+
+    /// Permanently freeze a config account, so that no instruction can ever
+    /// modify or close it again.
+    ///
+    /// Appends `state::FROZEN_MARKER` to the keys as a signer, moving the
+    /// data as `SetKeys` does. The last 33 bytes of the account must be zero.
+    ///
+    /// Accounts expected by this instruction:
+    ///
+    ///   0. `[writable]` The config account.
+    ///   1. ..1+N `[signer]` The N stored signers.
+    Freeze,
+}
+
+#[cfg(feature = "bincode")]
 fn initialize_account<T: Default + serde::Serialize>(config_pubkey: &Pubkey) -> Instruction {
     let account_metas = vec![AccountMeta::new(*config_pubkey, true)];
     let account_data = (ConfigKeys { keys: vec![] }, T::default());
@@ -14,6 +149,12 @@ fn initialize_account<T: Default + serde::Serialize>(config_pubkey: &Pubkey) ->
 }
 
 /// Create a new, empty configuration account
+///
+/// The account space is `max_config_space` plus the size of `keys`.
+#[cfg(feature = "bincode")]
+#[deprecated(
+    note = "Use `create_account_with_space`, whose `ConfigSpace` rejects space that overflows"
+)]
 pub fn create_account_with_max_config_space<T: Default + serde::Serialize>(
     from_account_pubkey: &Pubkey,
     config_account_pubkey: &Pubkey,
@@ -21,7 +162,39 @@ pub fn create_account_with_max_config_space<T: Default + serde::Serialize>(
     max_config_space: u64,
     keys: Vec<(Pubkey, bool)>,
 ) -> Vec<Instruction> {
-    let space = max_config_space.saturating_add(serialized_size(&ConfigKeys { keys }).unwrap());
+    // Space that can't be computed can't be allocated either, so leave the
+    // system program to reject it.
+    let space =
+        ConfigSpace::for_keys(&keys, max_config_space).map_or(u64::MAX, |space| space.space());
+    create_account_instructions::<T>(from_account_pubkey, config_account_pubkey, lamports, space)
+}
+
+/// Create a new, empty configuration account with the given space
+///
+/// Use [`ConfigSpace`] to compute the space for the account's keys and
+/// largest payload, and its rent-exempt `lamports`.
+#[cfg(feature = "bincode")]
+pub fn create_account_with_space<T: Default + serde::Serialize>(
+    from_account_pubkey: &Pubkey,
+    config_account_pubkey: &Pubkey,
+    lamports: u64,
+    space: &ConfigSpace,
+) -> Vec<Instruction> {
+    create_account_instructions::<T>(
+        from_account_pubkey,
+        config_account_pubkey,
+        lamports,
+        space.space(),
+    )
+}
+
+#[cfg(feature = "bincode")]
+fn create_account_instructions<T: Default + serde::Serialize>(
+    from_account_pubkey: &Pubkey,
+    config_account_pubkey: &Pubkey,
+    lamports: u64,
+    space: u64,
+) -> Vec<Instruction> {
     vec![
         solana_system_interface::instruction::create_account(
             from_account_pubkey,
@@ -35,6 +208,7 @@ pub fn create_account_with_max_config_space<T: Default + serde::Serialize>(
 }
 
 /// Store new data in a configuration account
+#[cfg(feature = "bincode")]
 pub fn store<T: serde::Serialize>(
     config_account_pubkey: &Pubkey,
     is_config_signer: bool,
@@ -50,3 +224,383 @@ pub fn store<T: serde::Serialize>(
     let account_data = (ConfigKeys { keys }, data);
     Instruction::new_with_bincode(id(), &account_data, account_metas)
 }
+
+/// Store new data in a configuration account with a signer threshold,
+/// signed by only the given subset of its stored signers
+///
+/// The signer keys and threshold marker in `keys` must match those already
+/// stored in the account.
+#[cfg(feature = "bincode")]
+pub fn store_with_signers<T: serde::Serialize>(
+    config_account_pubkey: &Pubkey,
+    is_config_signer: bool,
+    keys: Vec<(Pubkey, bool)>,
+    data: &T,
+    signers: &[Pubkey],
+) -> Instruction {
+    let mut account_metas = vec![AccountMeta::new(*config_account_pubkey, is_config_signer)];
+    for signer_pubkey in signers {
+        if signer_pubkey != config_account_pubkey {
+            account_metas.push(AccountMeta::new(*signer_pubkey, true));
+        }
+    }
+    let account_data = (ConfigKeys { keys }, data);
+    Instruction::new_with_bincode(id(), &account_data, account_metas)
+}
+
+#[cfg(feature = "bincode")]
+fn extension_instruction(
+    instruction: &ConfigInstruction,
+    config_account_pubkey: &Pubkey,
+    mut account_metas: Vec<AccountMeta>,
+    signers: &[Pubkey],
+) -> Instruction {
+    // The config account signs in place of any stored signer equal to it,
+    // and when there are no stored signers at all.
+    let is_config_signer =
+        signers.is_empty() || signers.iter().any(|signer| signer == config_account_pubkey);
+    account_metas[0].is_signer = is_config_signer;
+    for signer_pubkey in signers {
+        if signer_pubkey != config_account_pubkey {
+            account_metas.push(AccountMeta::new_readonly(*signer_pubkey, true));
+        }
+    }
+    let mut data = vec![EXTENSION_PREFIX];
+    data.extend(bincode::serialize(instruction).unwrap());
+    Instruction::new_with_bytes(id(), &data, account_metas)
+}
+
+/// Close a configuration account, transferring its lamports to
+/// `destination_pubkey`
+///
+/// `signers` are the signer keys currently stored in the account.
+#[cfg(feature = "bincode")]
+pub fn close(
+    config_account_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    signers: &[Pubkey],
+) -> Instruction {
+    extension_instruction(
+        &ConfigInstruction::Close,
+        config_account_pubkey,
+        vec![
+            AccountMeta::new(*config_account_pubkey, false),
+            AccountMeta::new(*destination_pubkey, false),
+        ],
+        signers,
+    )
+}
+
+/// Resize a configuration account's data to `new_len` bytes, topping up or
+/// refunding rent through `funding_pubkey`
+///
+/// `signers` are the signer keys currently stored in the account.
+#[cfg(feature = "bincode")]
+pub fn resize(
+    config_account_pubkey: &Pubkey,
+    funding_pubkey: &Pubkey,
+    new_len: u64,
+    signers: &[Pubkey],
+) -> Instruction {
+    extension_instruction(
+        &ConfigInstruction::Resize { new_len },
+        config_account_pubkey,
+        vec![
+            AccountMeta::new(*config_account_pubkey, false),
+            AccountMeta::new(*funding_pubkey, true),
+            AccountMeta::new_readonly(solana_sdk_ids::system_program::id(), false),
+        ],
+        signers,
+    )
+}
+
+/// Write `data` into a configuration account at `offset` bytes past its keys
+///
+/// `signers` are the signer keys currently stored in the account.
+#[cfg(feature = "bincode")]
+pub fn write(
+    config_account_pubkey: &Pubkey,
+    offset: u32,
+    data: Vec<u8>,
+    signers: &[Pubkey],
+) -> Instruction {
+    extension_instruction(
+        &ConfigInstruction::Write { offset, data },
+        config_account_pubkey,
+        vec![AccountMeta::new(*config_account_pubkey, false)],
+        signers,
+    )
+}
+
+/// Replace a configuration account's keys, keeping its data
+///
+/// `signers` are the signer keys currently stored in the account.
+#[cfg(feature = "bincode")]
+pub fn set_keys(
+    config_account_pubkey: &Pubkey,
+    keys: Vec<(Pubkey, bool)>,
+    signers: &[Pubkey],
+) -> Instruction {
+    extension_instruction(
+        &ConfigInstruction::SetKeys {
+            keys: ConfigKeys { keys },
+        },
+        config_account_pubkey,
+        vec![AccountMeta::new(*config_account_pubkey, false)],
+        signers,
+    )
+}
+
+/// Permanently freeze a configuration account
+///
+/// `signers` are the signer keys currently stored in the account.
+#[cfg(feature = "bincode")]
+pub fn freeze(config_account_pubkey: &Pubkey, signers: &[Pubkey]) -> Instruction {
+    extension_instruction(
+        &ConfigInstruction::Freeze,
+        config_account_pubkey,
+        vec![AccountMeta::new(*config_account_pubkey, false)],
+        signers,
+    )
+}
+
+/// Make a `store` instruction conditional on the config account's current
+/// data hashing to `expected_hash`
+///
+/// `store_instruction` may come from either [`store`] or
+/// [`store_with_signers`].
+#[cfg(feature = "bincode")]
+pub fn compare_and_store(store_instruction: Instruction, expected_hash: &Hash) -> Instruction {
+    let instruction = ConfigInstruction::CompareAndStore {
+        expected_hash: *expected_hash,
+        input: store_instruction.data,
+    };
+    let mut data = vec![EXTENSION_PREFIX];
+    data.extend(bincode::serialize(&instruction).unwrap());
+    Instruction::new_with_bytes(id(), &data, store_instruction.accounts)
+}
+
+/// A parsed `store` instruction.
+#[cfg(feature = "bincode")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParsedStoreInstruction<'a> {
+    /// The config account.
+    pub config_account: &'a AccountMeta,
+    /// The new keys.
+    pub keys: ConfigKeysRef<'a>,
+    /// The new config data following the keys.
+    pub data: &'a [u8],
+    /// The accounts signing for the signer keys, in the order of the keys.
+    pub signers: Vec<&'a AccountMeta>,
+}
+
+/// Parse a `store` instruction, checking it against the rules the program
+/// applies regardless of the config account's current state
+///
+/// Signer accounts are matched positionally against the signer keys, unless
+/// the keys record a signer threshold, in which case they are matched by key
+/// and only the threshold need sign. The program may still reject the
+/// instruction based on the keys already stored in the account.
+#[cfg(feature = "bincode")]
+pub fn parse_store_instruction(
+    instruction: &Instruction,
+) -> Result<ParsedStoreInstruction<'_>, StoreError> {
+    if instruction.program_id != id() {
+        return Err(StoreError::IncorrectProgramId);
+    }
+    parse_store(&instruction.data, &instruction.accounts)
+}
+
+/// Parse `store` instruction data and accounts
+///
+/// See [`parse_store_instruction`].
+#[cfg(feature = "bincode")]
+pub fn parse_store<'a>(
+    data: &'a [u8],
+    accounts: &'a [AccountMeta],
+) -> Result<ParsedStoreInstruction<'a>, StoreError> {
+    if data.first() == Some(&EXTENSION_PREFIX) {
+        return Err(StoreError::NotStore);
+    }
+    let keys = parse_store_keys(data)?;
+
+    let config_account = accounts.first().ok_or(StoreError::MissingConfigAccount)?;
+
+    match check_threshold(&keys)? {
+        Some(threshold) => check_stored_signers(
+            config_account,
+            keys.signers(),
+            Some(threshold),
+            &accounts[1..],
+        )?,
+        None => {
+            if keys.signers().next().is_none() && !config_account.is_signer {
+                return Err(StoreError::MissingSignature(config_account.pubkey));
+            }
+            check_signers_in_order(&keys, accounts, None)?;
+        }
+    }
+
+    check_unique_keys(&keys)?;
+
+    let signers = keys
+        .signers()
+        .filter_map(|signer| {
+            accounts
+                .iter()
+                .find(|account| account.pubkey == *signer && account.is_signer)
+        })
+        .collect();
+
+    Ok(ParsedStoreInstruction {
+        config_account,
+        keys,
+        data: keys.data(),
+        signers,
+    })
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use {super::*, crate::state::threshold_marker};
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_create_account() {
+        let (from, config) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let keys = vec![(Pubkey::new_unique(), true)];
+        let space = ConfigSpace::for_keys(&keys, 100).unwrap();
+        let instructions = create_account_with_space::<u64>(&from, &config, 1, &space);
+        assert_eq!(
+            instructions[0],
+            solana_system_interface::instruction::create_account(&from, &config, 1, 134, &id())
+        );
+        assert_eq!(
+            instructions,
+            create_account_with_max_config_space::<u64>(&from, &config, 1, 100, keys)
+        );
+
+        // Overflowing space is left for the system program to reject.
+        let instructions =
+            create_account_with_max_config_space::<u64>(&from, &config, 1, u64::MAX, vec![]);
+        assert_eq!(
+            instructions[0],
+            solana_system_interface::instruction::create_account(
+                &from,
+                &config,
+                1,
+                u64::MAX,
+                &id()
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_store() {
+        let config = Pubkey::new_unique();
+        let signer0 = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let keys = vec![
+            (Pubkey::new_unique(), false),
+            (signer0, true),
+            (signer1, true),
+        ];
+
+        let instruction = store(&config, false, keys.clone(), &42u64);
+        let parsed = parse_store_instruction(&instruction).unwrap();
+        assert_eq!(parsed.config_account.pubkey, config);
+        assert_eq!(parsed.keys.to_config_keys().keys, keys);
+        assert_eq!(parsed.data, 42u64.to_le_bytes());
+        assert_eq!(
+            parsed
+                .signers
+                .iter()
+                .map(|account| account.pubkey)
+                .collect::<Vec<_>>(),
+            vec![signer0, signer1]
+        );
+
+        let mut bad_instruction = instruction.clone();
+        bad_instruction.accounts.swap(1, 2);
+        assert_eq!(
+            parse_store_instruction(&bad_instruction),
+            Err(StoreError::SignerMismatch {
+                index: 1,
+                expected: signer0
+            })
+        );
+
+        let mut bad_instruction = instruction.clone();
+        bad_instruction.accounts[2].is_signer = false;
+        assert_eq!(
+            parse_store_instruction(&bad_instruction),
+            Err(StoreError::MissingSignature(signer1))
+        );
+
+        let mut bad_instruction = instruction.clone();
+        bad_instruction.accounts.pop();
+        assert_eq!(
+            parse_store_instruction(&bad_instruction),
+            Err(StoreError::MissingSignerAccount(signer1))
+        );
+
+        let mut bad_instruction = instruction;
+        bad_instruction.program_id = Pubkey::new_unique();
+        assert_eq!(
+            parse_store_instruction(&bad_instruction),
+            Err(StoreError::IncorrectProgramId)
+        );
+
+        let keys = vec![(signer0, true), (signer0, true)];
+        let instruction = store(&config, false, keys, &42u64);
+        assert_eq!(
+            parse_store_instruction(&instruction),
+            Err(StoreError::DuplicateKeys)
+        );
+
+        let instruction = write(&config, 0, vec![1], &[]);
+        assert_eq!(
+            parse_store_instruction(&instruction),
+            Err(StoreError::NotStore)
+        );
+    }
+
+    #[test]
+    fn test_parse_store_with_threshold() {
+        let config = Pubkey::new_unique();
+        let signer0 = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let signer2 = Pubkey::new_unique();
+        let keys = vec![
+            (signer0, true),
+            (signer1, true),
+            (signer2, true),
+            (threshold_marker(2), false),
+        ];
+
+        let instruction =
+            store_with_signers(&config, false, keys.clone(), &0u8, &[signer2, signer1]);
+        let parsed = parse_store_instruction(&instruction).unwrap();
+        assert_eq!(
+            parsed
+                .signers
+                .iter()
+                .map(|account| account.pubkey)
+                .collect::<Vec<_>>(),
+            vec![signer1, signer2]
+        );
+
+        let instruction = store_with_signers(&config, false, keys, &0u8, &[signer0]);
+        assert_eq!(
+            parse_store_instruction(&instruction),
+            Err(StoreError::TooFewSigners)
+        );
+
+        let keys = vec![(signer0, true), (threshold_marker(2), false)];
+        let instruction = store(&config, false, keys, &0u8);
+        assert_eq!(
+            parse_store_instruction(&instruction),
+            Err(StoreError::InvalidThreshold)
+        );
+    }
+}
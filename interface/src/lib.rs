@@ -1,6 +1,20 @@
+//! The config program interface.
+//!
+//! Without the default `std` feature the crate is `no_std`, and needs only
+//! `alloc`. The `ConfigKeys` layout, the zero-copy [`state::ConfigKeysRef`]
+//! reader and the `store` checks in [`validate`] are always available;
+//! instruction builders and payload (de)serialization need `std`, through the
+//! `bincode`, `borsh` or `serde` features.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(clippy::arithmetic_side_effects)]
-#[cfg(feature = "bincode")]
+
+extern crate alloc;
+
 pub mod instruction;
+pub mod space;
+pub mod stake_config;
 pub mod state;
+pub mod validate;
+pub mod validator_info;
 pub use solana_sdk_ids::config::id;
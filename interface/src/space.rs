@@ -0,0 +1,186 @@
+//! Account space and rent-exempt balances for config accounts.
+//!
+//! A config account holds its `ConfigKeys` followed by the payload, and every
+//! `store` rewrites both. `store` never resizes the account, so it should be
+//! created with enough space for the largest keys and payload it will hold;
+//! otherwise it must be grown with
+//! [`ConfigInstruction::Resize`](crate::instruction::ConfigInstruction::Resize)
+//! first.
+
+use {crate::state::config_keys_size, core::fmt, solana_pubkey::Pubkey, solana_rent::Rent};
+
+/// The maximum size of account data, 10 MiB.
+pub const MAX_PERMITTED_DATA_LENGTH: u64 = 10 * 1024 * 1024;
+
+/// Errors returned when computing config account space.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpaceError {
+    /// There are more keys than a `ShortU16` length can encode.
+    TooManyKeys,
+    /// The space overflows, or exceeds [`MAX_PERMITTED_DATA_LENGTH`].
+    SpaceOverflow,
+    /// The rent-exempt minimum balance overflows.
+    RentOverflow,
+    /// The keys and payload don't fit in the account.
+    InsufficientSpace,
+    /// The payload could not be serialized.
+    InvalidPayload,
+}
+
+impl fmt::Display for SpaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyKeys => f.write_str("too many config keys"),
+            Self::SpaceOverflow => f.write_str("config account space too large"),
+            Self::RentOverflow => f.write_str("rent-exempt minimum balance overflows"),
+            Self::InsufficientSpace => f.write_str("keys and payload don't fit in the account"),
+            Self::InvalidPayload => f.write_str("payload could not be serialized"),
+        }
+    }
+}
+
+impl core::error::Error for SpaceError {}
+
+/// The space of a config account, split between its keys and its payload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConfigSpace {
+    keys_len: u64,
+    max_payload_len: u64,
+}
+
+impl ConfigSpace {
+    /// The space for `num_keys` keys and a payload of up to
+    /// `max_payload_len` bytes.
+    pub fn new(num_keys: usize, max_payload_len: u64) -> Result<Self, SpaceError> {
+        if num_keys > usize::from(u16::MAX) {
+            return Err(SpaceError::TooManyKeys);
+        }
+        let keys_len = config_keys_size(num_keys) as u64;
+        keys_len
+            .checked_add(max_payload_len)
+            .filter(|space| *space <= MAX_PERMITTED_DATA_LENGTH)
+            .ok_or(SpaceError::SpaceOverflow)?;
+        Ok(Self {
+            keys_len,
+            max_payload_len,
+        })
+    }
+
+    /// The space for `keys` and a payload of up to `max_payload_len` bytes.
+    pub fn for_keys(keys: &[(Pubkey, bool)], max_payload_len: u64) -> Result<Self, SpaceError> {
+        Self::new(keys.len(), max_payload_len)
+    }
+
+    /// The exact space for `keys` and `payload`, as serialized by `store`.
+    #[cfg(feature = "bincode")]
+    pub fn for_payload<T: serde::Serialize>(
+        keys: &[(Pubkey, bool)],
+        payload: &T,
+    ) -> Result<Self, SpaceError> {
+        let payload_len =
+            bincode::serialized_size(payload).map_err(|_| SpaceError::InvalidPayload)?;
+        Self::for_keys(keys, payload_len)
+    }
+
+    /// The serialized size of the keys, which is also the offset of the
+    /// payload within the account data.
+    pub fn keys_len(&self) -> u64 {
+        self.keys_len
+    }
+
+    /// The space reserved for the payload.
+    pub fn max_payload_len(&self) -> u64 {
+        self.max_payload_len
+    }
+
+    /// The total account space.
+    pub fn space(&self) -> u64 {
+        // Checked on construction.
+        self.keys_len + self.max_payload_len
+    }
+
+    /// The minimum balance for the account to be rent exempt.
+    pub fn minimum_balance(&self, rent: &Rent) -> Result<u64, SpaceError> {
+        rent.try_minimum_balance(self.space() as usize)
+            .ok_or(SpaceError::RentOverflow)
+    }
+
+    /// The space left over in the account after a `store` of `num_keys` keys
+    /// and a payload of `payload_len` bytes, available to later stores.
+    pub fn headroom(&self, num_keys: usize, payload_len: u64) -> Result<u64, SpaceError> {
+        let used = Self::new(num_keys, payload_len)?.space();
+        self.space()
+            .checked_sub(used)
+            .ok_or(SpaceError::InsufficientSpace)
+    }
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use {
+        super::*,
+        crate::{instruction::store, state::ConfigKeys, validator_info},
+    };
+
+    #[test]
+    fn test_config_space() {
+        let keys = vec![(Pubkey::new_unique(), true), (Pubkey::new_unique(), false)];
+        let payload = (42u64, "config".to_string());
+
+        let space = ConfigSpace::for_payload(&keys, &payload).unwrap();
+        let instruction = store(&Pubkey::new_unique(), true, keys.clone(), &payload);
+        assert_eq!(space.space(), instruction.data.len() as u64);
+        assert_eq!(
+            space.keys_len(),
+            bincode::serialized_size(&ConfigKeys { keys: keys.clone() }).unwrap()
+        );
+        assert_eq!(space.max_payload_len(), 8 + 8 + 6);
+        assert_eq!(space.headroom(keys.len(), 22), Ok(0));
+
+        let rent = Rent::default();
+        assert_eq!(
+            space.minimum_balance(&rent),
+            Ok(rent.minimum_balance(instruction.data.len()))
+        );
+
+        // Room for a larger payload, or fewer keys, later.
+        let space = ConfigSpace::for_keys(&keys, 100).unwrap();
+        assert_eq!(space.space(), 1 + 2 * 33 + 100);
+        assert_eq!(space.headroom(2, 22), Ok(78));
+        assert_eq!(space.headroom(1, 22), Ok(78 + 33));
+        assert_eq!(space.headroom(2, 100), Ok(0));
+        assert_eq!(space.headroom(2, 101), Err(SpaceError::InsufficientSpace));
+        assert_eq!(space.headroom(3, 100), Err(SpaceError::InsufficientSpace));
+
+        assert_eq!(
+            ConfigSpace::new(0, validator_info::MAX_VALIDATOR_INFO)
+                .unwrap()
+                .space(),
+            1 + validator_info::MAX_VALIDATOR_INFO
+        );
+    }
+
+    #[test]
+    fn test_config_space_overflow() {
+        assert_eq!(
+            ConfigSpace::new(usize::from(u16::MAX) + 1, 0),
+            Err(SpaceError::TooManyKeys)
+        );
+        assert_eq!(
+            ConfigSpace::new(1, u64::MAX),
+            Err(SpaceError::SpaceOverflow)
+        );
+        assert_eq!(
+            ConfigSpace::new(0, MAX_PERMITTED_DATA_LENGTH),
+            Err(SpaceError::SpaceOverflow)
+        );
+        let space = ConfigSpace::new(0, MAX_PERMITTED_DATA_LENGTH - 1).unwrap();
+        assert_eq!(space.space(), MAX_PERMITTED_DATA_LENGTH);
+        assert_eq!(space.headroom(1, u64::MAX), Err(SpaceError::SpaceOverflow));
+
+        assert_eq!(
+            space.minimum_balance(&Rent::with_lamports_per_byte(u64::MAX)),
+            Err(SpaceError::RentOverflow)
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! The legacy stake program config, stored in the config account at [`ID`].
+//!
+//! The account stores no keys, as genesis creates it, followed by a
+//! [`StakeConfig`]. With no stored signers, every `store` must be signed by
+//! the config account itself.
+
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use {crate::state::PayloadError, alloc::vec::Vec, solana_pubkey::Pubkey};
+#[cfg(feature = "bincode")]
+use {
+    crate::{
+        instruction::store as store_config,
+        state::{ConfigAccount, ConfigKeys},
+    },
+    solana_instruction::Instruction,
+};
+
+/// The address of the stake config account.
+pub const ID: Pubkey = solana_pubkey::pubkey!("StakeConfig11111111111111111111111111111111");
+
+/// The default rate at which stake warms up and cools down per epoch.
+pub const DEFAULT_WARMUP_COOLDOWN_RATE: f64 = 0.25;
+
+/// The default slash penalty, 5% of `u8::MAX`.
+pub const DEFAULT_SLASH_PENALTY: u8 = 12;
+
+/// The payload of the stake config account.
+///
+/// Under borsh, a NaN `warmup_cooldown_rate` can be neither serialized nor
+/// deserialized.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StakeConfig {
+    /// The fraction of the effective stake that can warm up or cool down in
+    /// an epoch.
+    pub warmup_cooldown_rate: f64,
+    /// The fraction of stake to slash, in 256ths.
+    pub slash_penalty: u8,
+}
+
+impl Default for StakeConfig {
+    fn default() -> Self {
+        Self {
+            warmup_cooldown_rate: DEFAULT_WARMUP_COOLDOWN_RATE,
+            slash_penalty: DEFAULT_SLASH_PENALTY,
+        }
+    }
+}
+
+impl StakeConfig {
+    /// Check that the warmup/cooldown rate is a fraction above zero.
+    pub fn check(&self) -> Result<(), PayloadError> {
+        if !(self.warmup_cooldown_rate > 0.0 && self.warmup_cooldown_rate <= 1.0) {
+            return Err(PayloadError::InvalidValue("warmup_cooldown_rate"));
+        }
+        Ok(())
+    }
+}
+
+/// The keys of the stake config account, which are empty.
+pub fn keys() -> Vec<(Pubkey, bool)> {
+    Vec::new()
+}
+
+/// Parse stake config account data.
+#[cfg(feature = "bincode")]
+pub fn parse(account_data: &[u8]) -> Result<StakeConfig, PayloadError> {
+    let ConfigAccount {
+        keys: stored, data, ..
+    } = ConfigAccount::<StakeConfig>::from_bytes(account_data)?;
+    if stored.keys != keys() {
+        return Err(PayloadError::InvalidKeys);
+    }
+    data.check()?;
+    Ok(data)
+}
+
+/// Serialize the stake config account data, such as for a genesis account.
+#[cfg(feature = "bincode")]
+pub fn account_data(stake_config: &StakeConfig) -> Result<Vec<u8>, PayloadError> {
+    stake_config.check()?;
+    ConfigAccount::new(ConfigKeys { keys: keys() }, stake_config)
+        .and_then(|account| account.to_bytes())
+        .map_err(|_| PayloadError::InvalidAccountData)
+}
+
+/// Store a new stake config
+///
+/// The stake config account must sign.
+#[cfg(feature = "bincode")]
+pub fn store(stake_config: &StakeConfig) -> Result<Instruction, PayloadError> {
+    stake_config.check()?;
+    Ok(store_config(&ID, true, keys(), stake_config))
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use {super::*, solana_instruction::AccountMeta};
+
+    #[test]
+    fn test_stake_config() {
+        let stake_config = StakeConfig::default();
+        let account_data = account_data(&stake_config).unwrap();
+        assert_eq!(account_data.len(), 1 + 8 + 1);
+        assert_eq!(parse(&account_data), Ok(stake_config));
+
+        let instruction = store(&stake_config).unwrap();
+        assert_eq!(instruction.accounts, vec![AccountMeta::new(ID, true)]);
+        assert_eq!(instruction.data, account_data);
+
+        for warmup_cooldown_rate in [0.0, -0.25, 1.5, f64::NAN, f64::INFINITY] {
+            let stake_config = StakeConfig {
+                warmup_cooldown_rate,
+                ..StakeConfig::default()
+            };
+            assert_eq!(
+                store(&stake_config),
+                Err(PayloadError::InvalidValue("warmup_cooldown_rate"))
+            );
+        }
+
+        for keys in [vec![(ID, false)], vec![(ID, true)]] {
+            let account_data =
+                bincode::serialize(&(ConfigKeys { keys }, StakeConfig::default())).unwrap();
+            assert_eq!(parse(&account_data), Err(PayloadError::InvalidKeys));
+        }
+    }
+
+    #[test]
+    fn test_genesis_stake_config() {
+        // The account as genesis creates it: no keys, a warmup/cooldown rate
+        // of 0.25 and a slash penalty of 12.
+        let account_data = [0, 0, 0, 0, 0, 0, 0, 0xD0, 0x3F, 12];
+        assert_eq!(parse(&account_data), Ok(StakeConfig::default()));
+        assert_eq!(
+            super::account_data(&StakeConfig::default()).unwrap(),
+            account_data
+        );
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_stake_config_borsh() {
+        let keys = ConfigKeys { keys: keys() };
+        for stake_config in [
+            StakeConfig::default(),
+            StakeConfig {
+                warmup_cooldown_rate: 0.09,
+                slash_penalty: 255,
+            },
+        ] {
+            let bytes = borsh::to_vec(&(&keys, &stake_config)).unwrap();
+            assert_eq!(bytes, account_data(&stake_config).unwrap());
+            assert_eq!(
+                borsh::from_slice::<(ConfigKeys, StakeConfig)>(&bytes).unwrap(),
+                (keys.clone(), stake_config)
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use solana_pubkey::Pubkey;
+use {alloc::vec::Vec, core::fmt, solana_hash::Hash, solana_pubkey::Pubkey};
 #[cfg(feature = "serde")]
 use {
     serde_derive::{Deserialize, Serialize},
@@ -6,7 +6,7 @@ use {
 };
 
 /// A collection of keys to be stored in Config account data.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ConfigKeys {
     // Each key tuple comprises a unique `Pubkey` identifier,
@@ -15,6 +15,130 @@ pub struct ConfigKeys {
     pub keys: Vec<(Pubkey, bool)>,
 }
 
+/// The first 31 bytes of a signer threshold marker key.
+///
+/// A config account opts into M-of-N signing by storing a non-signer key
+/// made of this prefix followed by a single byte M. Updates then need only M
+/// of the stored signers, rather than all of them.
+///
+/// This departs from the original builtin, which treats every key alike.
+/// Legacy `store` instructions follow the marker rules too: a non-signer
+/// key with this prefix must record a valid threshold, or the `store` fails
+/// with [`StoreError::InvalidThreshold`], and an account storing a threshold
+/// is updated under the threshold rules. Keys the builtin accepted as
+/// ordinary non-signer keys can therefore be rejected.
+///
+/// [`StoreError::InvalidThreshold`]: crate::validate::StoreError::InvalidThreshold
+pub const THRESHOLD_MARKER_PREFIX: [u8; 31] = *b"ConfigSignerThresholdMarker\0\0\0\0";
+
+/// Create the marker key for a signer threshold of `threshold`.
+pub fn threshold_marker(threshold: u8) -> Pubkey {
+    let mut bytes = [0; 32];
+    bytes[..31].copy_from_slice(&THRESHOLD_MARKER_PREFIX);
+    bytes[31] = threshold;
+    Pubkey::new_from_array(bytes)
+}
+
+/// Get the signer threshold recorded by a marker key, if `pubkey` is one.
+pub fn parse_threshold_marker(pubkey: &Pubkey) -> Option<u8> {
+    let bytes = pubkey.as_array();
+    (bytes[..31] == THRESHOLD_MARKER_PREFIX).then_some(bytes[31])
+}
+
+/// The signer key marking a config account as frozen.
+///
+/// `ConfigInstruction::Freeze` stores this key as a signer. Nobody holds its
+/// private key, and the program rejects every instruction on an account that
+/// stores it, so the account can never change again.
+///
+/// This departs from the original builtin, which treats this key as an
+/// ordinary signer. A legacy `store` on an account storing it fails with
+/// [`StoreError::AccountFrozen`] as soon as the new keys are parsed and the
+/// account owner is checked, ahead of the builtin's checks on signers, keys,
+/// data length and writability.
+///
+/// [`StoreError::AccountFrozen`]: crate::validate::StoreError::AccountFrozen
+pub const FROZEN_MARKER: Pubkey =
+    Pubkey::new_from_array(*b"ConfigAccountFrozenMarker\0\0\0\0\0\0\0");
+
+impl ConfigKeys {
+    /// The serialized size of the keys, which is also the offset of the
+    /// config data within the account data.
+    pub fn serialized_size(&self) -> usize {
+        config_keys_size(self.keys.len())
+    }
+
+    /// Whether the keys mark the account as frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.keys.contains(&(FROZEN_MARKER, true))
+    }
+
+    /// The signer threshold recorded in the keys, if any.
+    ///
+    /// Only non-signer marker keys are considered.
+    pub fn threshold(&self) -> Option<u8> {
+        self.keys
+            .iter()
+            .filter(|(_, is_signer)| !*is_signer)
+            .find_map(|(pubkey, _)| parse_threshold_marker(pubkey))
+    }
+}
+
+// `ConfigKeys` has the same encoding under borsh as under bincode: a
+// `ShortU16` length followed by 33-byte entries.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for ConfigKeys {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        let len = u16::try_from(self.keys.len()).map_err(|_| {
+            borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "too many config keys")
+        })?;
+        let mut rem = len;
+        loop {
+            let mut byte = (rem & 0x7F) as u8;
+            rem >>= 7;
+            if rem != 0 {
+                byte |= 0x80;
+            }
+            writer.write_all(&[byte])?;
+            if rem == 0 {
+                break;
+            }
+        }
+        for entry in &self.keys {
+            entry.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for ConfigKeys {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        // Read the length bytes up to the first without the continuation bit,
+        // then decode them with the same rules as bincode.
+        let mut len_bytes = [0u8; 3];
+        let mut len_bytes_read = 0;
+        for byte in len_bytes.iter_mut() {
+            reader.read_exact(core::slice::from_mut(byte))?;
+            len_bytes_read += 1;
+            if *byte & 0x80 == 0 {
+                break;
+            }
+        }
+        let (len, _) = solana_short_vec::decode_shortu16_len(&len_bytes[..len_bytes_read])
+            .map_err(|_| {
+                borsh::io::Error::new(
+                    borsh::io::ErrorKind::InvalidData,
+                    "invalid config keys length",
+                )
+            })?;
+        let keys = (0..len)
+            .map(|_| <(Pubkey, bool)>::deserialize_reader(reader))
+            .collect::<borsh::io::Result<_>>()?;
+        Ok(Self { keys })
+    }
+}
+
 /// Utility for extracting the `ConfigKeys` data from the account data.
 #[cfg(feature = "bincode")]
 pub fn get_config_data(bytes: &[u8]) -> Result<&[u8], bincode::Error> {
@@ -22,3 +146,567 @@ pub fn get_config_data(bytes: &[u8]) -> Result<&[u8], bincode::Error> {
         .and_then(|keys| bincode::serialized_size(&keys))
         .map(|offset| &bytes[offset as usize..])
 }
+
+/// The serialized size of a single `(Pubkey, bool)` entry in `ConfigKeys`.
+const CONFIG_KEY_LEN: usize = 32 + 1;
+
+/// The serialized size of `ConfigKeys` with `len` keys: the `ShortU16`
+/// length, of 1 to 3 bytes, followed by the entries.
+pub(crate) fn config_keys_size(len: usize) -> usize {
+    let prefix_len = match len {
+        0..=0x7F => 1,
+        0x80..=0x3FFF => 2,
+        _ => 3,
+    };
+    prefix_len + len * CONFIG_KEY_LEN
+}
+
+/// Errors returned when reading `ConfigKeys` from account data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConfigKeysError {
+    /// The `ShortU16` length prefix is malformed.
+    InvalidLength,
+    /// The buffer is too small for the number of keys declared.
+    BufferTooSmall,
+    /// A signer flag is neither `0` nor `1`.
+    InvalidSignerFlag,
+}
+
+impl fmt::Display for ConfigKeysError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("invalid config keys length"),
+            Self::BufferTooSmall => f.write_str("buffer too small for config keys"),
+            Self::InvalidSignerFlag => f.write_str("invalid config key signer flag"),
+        }
+    }
+}
+
+impl core::error::Error for ConfigKeysError {}
+
+/// Errors returned when reading or checking a well-known config payload,
+/// such as validator info.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PayloadError {
+    /// The account data could not be deserialized as the payload.
+    InvalidAccountData,
+    /// The keys don't have the payload's canonical layout.
+    InvalidKeys,
+    /// The serialized payload is larger than its maximum size.
+    PayloadTooLarge,
+    /// A payload field holds an invalid value.
+    InvalidValue(&'static str),
+}
+
+impl fmt::Display for PayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidAccountData => f.write_str("invalid payload account data"),
+            Self::InvalidKeys => f.write_str("config keys don't match the payload layout"),
+            Self::PayloadTooLarge => f.write_str("payload too large"),
+            Self::InvalidValue(field) => write!(f, "invalid value for {field}"),
+        }
+    }
+}
+
+impl core::error::Error for PayloadError {}
+
+/// A borrowed, allocation-free view of the `ConfigKeys` at the start of
+/// config account data.
+///
+/// Rejects the same malformed inputs as deserializing `ConfigKeys` with
+/// bincode, without allocating a vector for the keys.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConfigKeysRef<'a> {
+    keys: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> ConfigKeysRef<'a> {
+    /// Read the `ConfigKeys` from the start of `bytes`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ConfigKeysError> {
+        let (len, offset) = solana_short_vec::decode_shortu16_len(bytes)
+            .map_err(|_| ConfigKeysError::InvalidLength)?;
+        let rest = &bytes[offset..];
+        if rest.len() / CONFIG_KEY_LEN < len {
+            return Err(ConfigKeysError::BufferTooSmall);
+        }
+        let (keys, data) = rest.split_at(len * CONFIG_KEY_LEN);
+        if keys.chunks_exact(CONFIG_KEY_LEN).any(|entry| entry[32] > 1) {
+            return Err(ConfigKeysError::InvalidSignerFlag);
+        }
+        Ok(Self { keys, data })
+    }
+
+    /// The number of keys.
+    pub fn len(&self) -> usize {
+        self.keys.len() / CONFIG_KEY_LEN
+    }
+
+    /// Whether there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Get the key at `index`, and whether it is a signer.
+    pub fn get(&self, index: usize) -> Option<(&'a Pubkey, bool)> {
+        let start = index.checked_mul(CONFIG_KEY_LEN)?;
+        let entry = self.keys.get(start..start.checked_add(CONFIG_KEY_LEN)?)?;
+        Some(Self::entry(entry))
+    }
+
+    /// Iterate over each key, and whether it is a signer.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a Pubkey, bool)> + 'a {
+        self.keys.chunks_exact(CONFIG_KEY_LEN).map(Self::entry)
+    }
+
+    /// Iterate over the keys that are signers.
+    pub fn signers(&self) -> impl Iterator<Item = &'a Pubkey> + 'a {
+        self.iter()
+            .filter(|(_, is_signer)| *is_signer)
+            .map(|(pubkey, _)| pubkey)
+    }
+
+    /// Whether the keys mark the account as frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.signers().any(|signer| *signer == FROZEN_MARKER)
+    }
+
+    /// The signer threshold recorded in the keys, if any.
+    ///
+    /// Only non-signer marker keys are considered.
+    pub fn threshold(&self) -> Option<u8> {
+        self.iter()
+            .filter(|(_, is_signer)| !*is_signer)
+            .find_map(|(pubkey, _)| parse_threshold_marker(pubkey))
+    }
+
+    /// The serialized size of the keys, which is also the offset of the
+    /// config data within the account data.
+    pub fn serialized_size(&self) -> usize {
+        config_keys_size(self.len())
+    }
+
+    /// The account data following the keys.
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The SHA-256 hash of the account data following the keys, as expected
+    /// by `ConfigInstruction::CompareAndStore`.
+    pub fn data_hash(&self) -> Hash {
+        solana_sha256_hasher::hash(self.data)
+    }
+
+    /// Convert to an owned `ConfigKeys`.
+    pub fn to_config_keys(&self) -> ConfigKeys {
+        ConfigKeys {
+            keys: self
+                .iter()
+                .map(|(pubkey, is_signer)| (*pubkey, is_signer))
+                .collect(),
+        }
+    }
+
+    fn entry(entry: &'a [u8]) -> (&'a Pubkey, bool) {
+        (bytemuck::from_bytes(&entry[..32]), entry[32] == 1)
+    }
+}
+
+/// A config account's `ConfigKeys` and typed payload, parsed together from
+/// the account data.
+#[cfg(feature = "bincode")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigAccount<T> {
+    /// The keys stored in the account.
+    pub keys: ConfigKeys,
+    /// The typed payload stored after the keys.
+    pub data: T,
+    data_offset: usize,
+    unused_len: usize,
+}
+
+#[cfg(feature = "bincode")]
+impl<T: serde::de::DeserializeOwned + serde::Serialize> ConfigAccount<T> {
+    /// Deserialize the keys and payload from config account data.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PayloadError> {
+        let account = ConfigAccountRef::<T>::from_bytes(bytes)?;
+        let unused_len = account.unused_len();
+        Ok(Self {
+            keys: account.keys,
+            data: account.data,
+            data_offset: account.data_offset,
+            unused_len,
+        })
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T> ConfigAccount<T> {
+    /// Create a new config account view from keys and a payload.
+    pub fn new(keys: ConfigKeys, data: T) -> Result<Self, bincode::Error> {
+        let data_offset = bincode::serialized_size(&keys)? as usize;
+        Ok(Self {
+            keys,
+            data,
+            data_offset,
+            unused_len: 0,
+        })
+    }
+
+    /// The byte offset of the payload within the account data.
+    pub fn data_offset(&self) -> usize {
+        self.data_offset
+    }
+
+    /// The number of bytes left over in the account after the payload.
+    pub fn unused_len(&self) -> usize {
+        self.unused_len
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<T: serde::Serialize> ConfigAccount<T> {
+    /// Serialize the keys and payload, in the same format accepted by the
+    /// `store` instruction.
+    ///
+    /// Unused trailing capacity is not included.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        bincode::serialize(&(&self.keys, &self.data))
+    }
+}
+
+/// A config account's `ConfigKeys` and typed payload, where the payload may
+/// borrow from the account data.
+#[cfg(feature = "bincode")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigAccountRef<'a, T> {
+    /// The keys stored in the account.
+    pub keys: ConfigKeys,
+    /// The typed payload stored after the keys.
+    pub data: T,
+    data_offset: usize,
+    data_len: usize,
+    bytes: &'a [u8],
+}
+
+#[cfg(feature = "bincode")]
+impl<'a, T: serde::Deserialize<'a> + serde::Serialize> ConfigAccountRef<'a, T> {
+    /// Deserialize the keys and payload from config account data.
+    ///
+    /// The payload's serialized size is used to locate the end of the
+    /// payload, so `T` must also be serializable. Fails if that size runs
+    /// past the end of `bytes`.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, PayloadError> {
+        let invalid = |_| PayloadError::InvalidAccountData;
+        let keys = bincode::deserialize::<ConfigKeys>(bytes).map_err(invalid)?;
+        let data_offset = bincode::serialized_size(&keys).map_err(invalid)? as usize;
+        let data = bincode::deserialize::<T>(
+            bytes
+                .get(data_offset..)
+                .ok_or(PayloadError::InvalidAccountData)?,
+        )
+        .map_err(invalid)?;
+        let data_len = bincode::serialized_size(&data).map_err(invalid)? as usize;
+        if bytes.len().saturating_sub(data_offset) < data_len {
+            return Err(PayloadError::InvalidAccountData);
+        }
+        Ok(Self {
+            keys,
+            data,
+            data_offset,
+            data_len,
+            bytes,
+        })
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl<'a, T> ConfigAccountRef<'a, T> {
+    /// The byte offset of the payload within the account data.
+    pub fn data_offset(&self) -> usize {
+        self.data_offset
+    }
+
+    /// The serialized payload.
+    pub fn data_bytes(&self) -> &'a [u8] {
+        self.bytes
+            .get(self.data_offset..self.data_end())
+            .unwrap_or_default()
+    }
+
+    /// The bytes left over in the account after the payload.
+    pub fn unused_bytes(&self) -> &'a [u8] {
+        self.bytes.get(self.data_end()..).unwrap_or_default()
+    }
+
+    /// The number of bytes left over in the account after the payload.
+    pub fn unused_len(&self) -> usize {
+        self.unused_bytes().len()
+    }
+
+    fn data_end(&self) -> usize {
+        self.data_offset.saturating_add(self.data_len)
+    }
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use super::*;
+    #[cfg(feature = "borsh")]
+    use borsh::BorshDeserialize;
+
+    #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+    struct MyConfig {
+        item: u64,
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct MyConfigRef<'a> {
+        item: u64,
+        name: &'a str,
+    }
+
+    fn account_data(keys: &ConfigKeys, config: &MyConfig, unused_len: usize) -> Vec<u8> {
+        let mut bytes = bincode::serialize(&(keys, config)).unwrap();
+        bytes.resize(bytes.len() + unused_len, 0);
+        bytes
+    }
+
+    #[test]
+    fn test_config_keys_ref() {
+        let keys = ConfigKeys {
+            keys: vec![
+                (Pubkey::new_from_array([1; 32]), false),
+                (Pubkey::new_from_array([2; 32]), true),
+                (Pubkey::new_from_array([3; 32]), true),
+            ],
+        };
+        let mut bytes = bincode::serialize(&keys).unwrap();
+        bytes.extend_from_slice(&[7, 8, 9]);
+
+        let keys_ref = ConfigKeysRef::new(&bytes).unwrap();
+        assert_eq!(keys_ref.len(), 3);
+        assert!(!keys_ref.is_empty());
+        assert_eq!(keys_ref.get(1), Some((&keys.keys[1].0, true)));
+        assert_eq!(keys_ref.get(3), None);
+        assert_eq!(
+            keys_ref.signers().collect::<Vec<_>>(),
+            vec![&keys.keys[1].0, &keys.keys[2].0],
+        );
+        assert_eq!(keys_ref.to_config_keys(), keys);
+        assert_eq!(
+            keys_ref.serialized_size() as u64,
+            bincode::serialized_size(&keys).unwrap(),
+        );
+        assert_eq!(keys_ref.data(), &[7, 8, 9]);
+        assert_eq!(keys_ref.data(), get_config_data(&bytes).unwrap());
+
+        let empty = ConfigKeysRef::new(&[0]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.serialized_size(), 1);
+    }
+
+    #[test]
+    fn test_config_keys_serialized_size() {
+        // Lengths at each boundary of the 1, 2 and 3 byte `ShortU16` prefix.
+        for len in [0, 1, 0x7F, 0x80, 0x3FFF, 0x4000] {
+            let keys = ConfigKeys {
+                keys: vec![(Pubkey::default(), false); len],
+            };
+            assert_eq!(
+                keys.serialized_size() as u64,
+                bincode::serialized_size(&keys).unwrap(),
+            );
+            let bytes = bincode::serialize(&keys).unwrap();
+            assert_eq!(
+                ConfigKeysRef::new(&bytes).unwrap().serialized_size(),
+                bytes.len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_keys_ref_malformed() {
+        let keys = ConfigKeys {
+            keys: vec![(Pubkey::new_from_array([1; 32]), true)],
+        };
+        let bytes = bincode::serialize(&keys).unwrap();
+
+        let check = |bytes: &[u8], err: ConfigKeysError| {
+            assert_eq!(ConfigKeysRef::new(bytes), Err(err));
+            assert!(bincode::deserialize::<ConfigKeys>(bytes).is_err());
+        };
+
+        // Empty buffer.
+        check(&[], ConfigKeysError::InvalidLength);
+        // Non-canonical `ShortU16` encoding.
+        check(&[0x81, 0x00], ConfigKeysError::InvalidLength);
+        // Length larger than the buffer.
+        check(&bytes[..bytes.len() - 1], ConfigKeysError::BufferTooSmall);
+        let mut too_long = bytes.clone();
+        too_long[0] = 255;
+        check(&too_long, ConfigKeysError::BufferTooSmall);
+        // Signer flag out of range.
+        let mut bad_flag = bytes.clone();
+        bad_flag[33] = 2;
+        check(&bad_flag, ConfigKeysError::InvalidSignerFlag);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_config_keys_borsh() {
+        for len in [0, 1, 2, 37, 127, 128, 300, 16_384] {
+            let keys = ConfigKeys {
+                keys: (0..len)
+                    .map(|i| (Pubkey::new_unique(), i % 3 == 0))
+                    .collect(),
+            };
+            let bytes = borsh::to_vec(&keys).unwrap();
+            assert_eq!(bytes, bincode::serialize(&keys).unwrap());
+            assert_eq!(borsh::from_slice::<ConfigKeys>(&bytes).unwrap(), keys);
+            assert_eq!(
+                bincode::deserialize::<ConfigKeys>(&bytes).unwrap(),
+                borsh::from_slice::<ConfigKeys>(&bytes).unwrap()
+            );
+        }
+
+        // Both formats reject the same malformed encodings.
+        for bytes in [
+            // Alias encoding of zero keys.
+            vec![0x80, 0x00],
+            // Length longer than three bytes.
+            vec![0x80, 0x80, 0x80, 0x00],
+            // Length above `u16::MAX`.
+            vec![0xFF, 0xFF, 0x04],
+            // Missing keys.
+            vec![0x01],
+            // Signer flag other than zero or one.
+            [&[0x01][..], &[0; 32], &[2]].concat(),
+        ] {
+            assert!(bincode::deserialize::<ConfigKeys>(&bytes).is_err());
+            assert!(ConfigKeys::deserialize_reader(&mut bytes.as_slice()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_is_frozen() {
+        let mut keys = ConfigKeys {
+            keys: vec![(Pubkey::new_unique(), true), (FROZEN_MARKER, false)],
+        };
+        assert!(!keys.is_frozen());
+        let bytes = bincode::serialize(&keys).unwrap();
+        assert!(!ConfigKeysRef::new(&bytes).unwrap().is_frozen());
+
+        keys.keys.push((FROZEN_MARKER, true));
+        assert!(keys.is_frozen());
+        let bytes = bincode::serialize(&keys).unwrap();
+        assert!(ConfigKeysRef::new(&bytes).unwrap().is_frozen());
+    }
+
+    #[test]
+    fn test_threshold() {
+        let signer0 = Pubkey::new_from_array([1; 32]);
+        let signer1 = Pubkey::new_from_array([2; 32]);
+
+        assert_eq!(parse_threshold_marker(&threshold_marker(3)), Some(3));
+        assert_eq!(parse_threshold_marker(&signer0), None);
+
+        let mut keys = ConfigKeys {
+            keys: vec![(signer0, true), (signer1, true)],
+        };
+        assert_eq!(keys.threshold(), None);
+
+        // Markers flagged as signers are ignored.
+        keys.keys.push((threshold_marker(2), true));
+        assert_eq!(keys.threshold(), None);
+
+        keys.keys.push((threshold_marker(1), false));
+        assert_eq!(keys.threshold(), Some(1));
+
+        let bytes = bincode::serialize(&keys).unwrap();
+        assert_eq!(ConfigKeysRef::new(&bytes).unwrap().threshold(), Some(1));
+    }
+
+    #[test]
+    fn test_config_account_round_trip() {
+        let keys = ConfigKeys {
+            keys: vec![
+                (Pubkey::new_from_array([1; 32]), true),
+                (Pubkey::new_from_array([2; 32]), false),
+            ],
+        };
+        let config = MyConfig {
+            item: 42,
+            name: "config".to_string(),
+        };
+        let bytes = account_data(&keys, &config, 16);
+
+        let account = ConfigAccount::<MyConfig>::from_bytes(&bytes).unwrap();
+        assert_eq!(account.keys, keys);
+        assert_eq!(account.data, config);
+        assert_eq!(account.data_offset(), 1 + 2 * 33);
+        assert_eq!(account.unused_len(), 16);
+        assert_eq!(account.to_bytes().unwrap(), bytes[..bytes.len() - 16]);
+        assert_eq!(
+            &bytes[account.data_offset()..bytes.len() - 16],
+            get_config_data(&bytes[..bytes.len() - 16]).unwrap(),
+        );
+
+        let account = ConfigAccount::new(keys, config).unwrap();
+        assert_eq!(
+            ConfigAccount::<MyConfig>::from_bytes(&account.to_bytes().unwrap()).unwrap(),
+            account,
+        );
+    }
+
+    #[test]
+    fn test_config_account_ref() {
+        let keys = ConfigKeys::default();
+        let config = MyConfig {
+            item: 7,
+            name: "borrowed".to_string(),
+        };
+        let bytes = account_data(&keys, &config, 3);
+
+        let account = ConfigAccountRef::<MyConfigRef>::from_bytes(&bytes).unwrap();
+        assert_eq!(account.data.item, 7);
+        assert_eq!(account.data.name, "borrowed");
+        assert_eq!(account.data_offset(), 1);
+        assert_eq!(account.data_bytes(), bincode::serialize(&config).unwrap());
+        assert_eq!(account.unused_bytes(), &[0; 3]);
+    }
+
+    #[test]
+    fn test_config_account_bad_data() {
+        let keys = ConfigKeys::default();
+        let config = MyConfig::default();
+        let bytes = account_data(&keys, &config, 0);
+
+        assert!(ConfigAccount::<MyConfig>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(ConfigAccount::<MyConfig>::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_config_account_payload_reserializes_larger() {
+        // Deserializing reads only `item`, but serializing also writes `pad`,
+        // so the payload's serialized size runs past the end of the data.
+        #[derive(Debug, Deserialize, Serialize)]
+        struct Padded {
+            item: u8,
+            #[serde(skip_deserializing)]
+            pad: u64,
+        }
+
+        let bytes = [0, 7];
+        assert_eq!(
+            ConfigAccount::<Padded>::from_bytes(&bytes).unwrap_err(),
+            PayloadError::InvalidAccountData
+        );
+        assert_eq!(
+            ConfigAccountRef::<Padded>::from_bytes(&bytes).unwrap_err(),
+            PayloadError::InvalidAccountData
+        );
+    }
+}
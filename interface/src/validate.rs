@@ -0,0 +1,519 @@
+//! Checks applied by the program to `store` instructions, shared with
+//! off-chain callers.
+
+#[cfg(feature = "bincode")]
+use {
+    crate::instruction::EXTENSION_PREFIX,
+    solana_instruction::{AccountMeta, Instruction},
+};
+use {
+    crate::{
+        instruction::MAX_STORE_KEYS,
+        state::{parse_threshold_marker, ConfigKeysRef},
+    },
+    alloc::collections::BTreeSet,
+    core::fmt,
+    solana_account_info::AccountInfo,
+    solana_pubkey::Pubkey,
+};
+
+/// Errors returned when checking a `store` instruction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StoreError {
+    /// The instruction is not for the config program.
+    IncorrectProgramId,
+    /// The instruction data is for an instruction other than `store`.
+    NotStore,
+    /// The instruction data does not begin with valid `ConfigKeys`, or has
+    /// more than [`MAX_STORE_KEYS`] keys.
+    InvalidConfigKeys,
+    /// The instruction has no accounts.
+    MissingConfigAccount,
+    /// The config account data does not begin with valid `ConfigKeys`.
+    InvalidAccountState,
+    /// The config account is frozen.
+    AccountFrozen,
+    /// A signer key has no account in its expected position.
+    MissingSignerAccount(Pubkey),
+    /// The account for a signer key did not sign.
+    MissingSignature(Pubkey),
+    /// The account at `index` is not the expected signer.
+    SignerMismatch {
+        /// The position of the account.
+        index: usize,
+        /// The signer key expected at that position.
+        expected: Pubkey,
+    },
+    /// A signer key is not among the signers stored in the account.
+    UnknownSigner(Pubkey),
+    /// Fewer signers signed than the stored signers or signer threshold
+    /// require.
+    TooFewSigners,
+    /// The keys record more than one signer threshold, or one that is zero
+    /// or above the number of signer keys.
+    InvalidThreshold,
+    /// The signers or signer threshold differ from those stored in an
+    /// account with a signer threshold.
+    SignersChanged,
+    /// The keys contain duplicates.
+    DuplicateKeys,
+    /// The instruction data is larger than the config account.
+    InstructionDataTooLarge,
+    /// The config account is not writable.
+    ReadonlyDataModified,
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IncorrectProgramId => f.write_str("instruction is not for the config program"),
+            Self::NotStore => f.write_str("instruction is not a store instruction"),
+            Self::InvalidConfigKeys => f.write_str("invalid config keys"),
+            Self::MissingConfigAccount => f.write_str("missing config account"),
+            Self::InvalidAccountState => f.write_str("invalid config account data"),
+            Self::AccountFrozen => f.write_str("config account is frozen"),
+            Self::MissingSignerAccount(signer) => {
+                write!(f, "account {signer} is not in account list")
+            }
+            Self::MissingSignature(signer) => write!(f, "signer {signer} did not sign"),
+            Self::SignerMismatch { index, expected } => {
+                write!(f, "account {index} does not match signer {expected}")
+            }
+            Self::UnknownSigner(signer) => {
+                write!(f, "account {signer} is not in stored signer list")
+            }
+            Self::TooFewSigners => f.write_str("too few signers"),
+            Self::InvalidThreshold => f.write_str("invalid signer threshold"),
+            Self::SignersChanged => f.write_str("signers and threshold must match stored config"),
+            Self::DuplicateKeys => f.write_str("new config contains duplicate keys"),
+            Self::InstructionDataTooLarge => f.write_str("instruction data too large"),
+            Self::ReadonlyDataModified => f.write_str("config account is not writable"),
+        }
+    }
+}
+
+impl core::error::Error for StoreError {}
+
+/// The parts of an instruction account that the checks depend on.
+pub trait AccountView {
+    /// The account's address.
+    fn pubkey(&self) -> &Pubkey;
+    /// Whether the account signed.
+    fn is_signer(&self) -> bool;
+    /// Whether the account is writable.
+    fn is_writable(&self) -> bool;
+}
+
+impl AccountView for AccountInfo<'_> {
+    fn pubkey(&self) -> &Pubkey {
+        self.key
+    }
+
+    fn is_signer(&self) -> bool {
+        self.is_signer
+    }
+
+    fn is_writable(&self) -> bool {
+        self.is_writable
+    }
+}
+
+#[cfg(feature = "bincode")]
+impl AccountView for AccountMeta {
+    fn pubkey(&self) -> &Pubkey {
+        &self.pubkey
+    }
+
+    fn is_signer(&self) -> bool {
+        self.is_signer
+    }
+
+    fn is_writable(&self) -> bool {
+        self.is_writable
+    }
+}
+
+/// Read the new `ConfigKeys` from `store` instruction data.
+///
+/// The program caps the number of keys at [`MAX_STORE_KEYS`] by peeking the
+/// first byte of the `ShortU16` length, so a length that doesn't fit in one
+/// byte is rejected before any keys are read.
+///
+/// The original builtin deserialized the keys with bincode, limited to the
+/// 1232-byte packet size, so it could never accept more keys than this. But
+/// bincode allocates a vector of the encoded length before reading its
+/// elements, which for a large length exhausts the heap of the BPF program
+/// before the size limit is reached. The keys are instead bounded first and
+/// then read in place as a [`ConfigKeysRef`], without allocating.
+pub fn parse_store_keys(input: &[u8]) -> Result<ConfigKeysRef<'_>, StoreError> {
+    match input.first() {
+        Some(&len) if usize::from(len) <= MAX_STORE_KEYS => {
+            ConfigKeysRef::new(input).map_err(|_| StoreError::InvalidConfigKeys)
+        }
+        _ => Err(StoreError::InvalidConfigKeys),
+    }
+}
+
+/// Check a `store` instruction against the config account's current data,
+/// in the same order as the program.
+///
+/// `new_keys` are the keys read from the instruction data by
+/// [`parse_store_keys`], and `input_len` is the length of the instruction
+/// data. `accounts` are the instruction's accounts, starting with the config
+/// account. The program additionally checks that the config account is owned
+/// by it before these checks.
+///
+/// Unlike the original builtin, the checks follow signer threshold markers
+/// in the stored and new keys, and reject a frozen account before any other
+/// check; see [`THRESHOLD_MARKER_PREFIX`](crate::state::THRESHOLD_MARKER_PREFIX)
+/// and [`FROZEN_MARKER`](crate::state::FROZEN_MARKER).
+pub fn check_store<A: AccountView>(
+    new_keys: &ConfigKeysRef,
+    input_len: usize,
+    current_data: &[u8],
+    accounts: &[A],
+) -> Result<(), StoreError> {
+    let config_account = accounts.first().ok_or(StoreError::MissingConfigAccount)?;
+    let current_keys =
+        ConfigKeysRef::new(current_data).map_err(|_| StoreError::InvalidAccountState)?;
+    if current_keys.is_frozen() {
+        return Err(StoreError::AccountFrozen);
+    }
+
+    let threshold = current_keys.threshold();
+    let mut counter: usize = 0;
+    if let Some(threshold) = threshold {
+        // With a signer threshold, any M of the stored signers may sign, in
+        // any order. The signers and threshold themselves can't be changed
+        // by `store`.
+        check_stored_signers(
+            config_account,
+            current_keys.signers(),
+            Some(threshold),
+            &accounts[1..],
+        )?;
+        let new_signers = new_keys.signers().collect::<BTreeSet<_>>();
+        if new_signers != current_keys.signers().collect()
+            || new_keys.threshold() != Some(threshold)
+        {
+            return Err(StoreError::SignersChanged);
+        }
+    } else {
+        // Config account keypair must be a signer on account initialization,
+        // or when no signers specified in Config data.
+        if current_keys.signers().next().is_none() && !config_account.is_signer() {
+            return Err(StoreError::MissingSignature(*config_account.pubkey()));
+        }
+        counter = check_signers_in_order(new_keys, accounts, Some(&current_keys))?;
+    }
+
+    check_threshold(new_keys)?;
+
+    check_unique_keys(new_keys)?;
+
+    // Check for Config data signers not present in incoming account update.
+    if threshold.is_none() && current_keys.signers().count() > counter {
+        return Err(StoreError::TooFewSigners);
+    }
+
+    if current_data.len() < input_len {
+        return Err(StoreError::InstructionDataTooLarge);
+    }
+
+    // [Core BPF]:
+    // When a builtin program attempts to write to an executable or read-only
+    // account, it will be immediately rejected by the `TransactionContext`.
+    // However, BPF programs do not query the `TransactionContext` for the
+    // ability to perform a write. Instead, they perform writes at-will, and
+    // the loader will inspect the serialized account memory region for any
+    // account update violations _after_ the VM has completed execution.
+    //
+    // The loader's inspection will catch any unauthorized modifications,
+    // however, when the exact same data is written to the account, thus
+    // rendering the serialized account state unchanged, the program succeeds.
+    //
+    // In order to maximize backwards compatibility between the BPF version and
+    // its original builtin, we add this check from `TransactionContext` to the
+    // program directly, to throw even when the data being written is the same
+    // same as what's currently in the account.
+    //
+    // Since the account can never be executable and also owned by the config
+    // program, we'll just focus on readonly.
+    if !config_account.is_writable() {
+        return Err(StoreError::ReadonlyDataModified);
+    }
+
+    Ok(())
+}
+
+/// Check that the stored signers have signed, as required by every
+/// instruction other than `store`, and by `store` for accounts with a signer
+/// threshold.
+///
+/// Every stored signer must sign, or only `threshold` of them if given, or
+/// the config account itself if there are no stored signers. A stored signer
+/// equal to the config account is satisfied by the config account signing;
+/// others are looked up by key among `signer_accounts`.
+pub fn check_stored_signers<'a, A: AccountView>(
+    config_account: &A,
+    stored_signers: impl Iterator<Item = &'a Pubkey>,
+    threshold: Option<u8>,
+    signer_accounts: &[A],
+) -> Result<(), StoreError> {
+    let mut stored_signers = stored_signers.peekable();
+    if stored_signers.peek().is_none() {
+        if !config_account.is_signer() {
+            return Err(StoreError::MissingSignature(*config_account.pubkey()));
+        }
+        return Ok(());
+    }
+
+    let mut signed: usize = 0;
+    for signer in stored_signers {
+        let is_signed = if signer == config_account.pubkey() {
+            config_account.is_signer()
+        } else {
+            signer_accounts
+                .iter()
+                .any(|account| account.pubkey() == signer && account.is_signer())
+        };
+        if is_signed {
+            signed = signed.saturating_add(1);
+        } else if threshold.is_none() {
+            return Err(StoreError::MissingSignature(*signer));
+        }
+    }
+
+    if let Some(threshold) = threshold {
+        if signed < usize::from(threshold) {
+            return Err(StoreError::TooFewSigners);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check a `store` instruction off-chain, before submitting it.
+///
+/// `account_data` is the config account's current data, and `signers` are
+/// the keys that will sign the transaction. An account only counts as signing
+/// if it is both flagged as a signer in the instruction and in `signers`.
+/// The config account is assumed to be owned by the program.
+#[cfg(feature = "bincode")]
+pub fn preflight_store(
+    account_data: &[u8],
+    instruction: &Instruction,
+    signers: &[Pubkey],
+) -> Result<(), StoreError> {
+    if instruction.program_id != crate::id() {
+        return Err(StoreError::IncorrectProgramId);
+    }
+    if instruction.data.first() == Some(&EXTENSION_PREFIX) {
+        return Err(StoreError::NotStore);
+    }
+    let new_keys = parse_store_keys(&instruction.data)?;
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|account| AccountMeta {
+            is_signer: account.is_signer && signers.contains(&account.pubkey),
+            ..account.clone()
+        })
+        .collect::<Vec<_>>();
+    check_store(&new_keys, instruction.data.len(), account_data, &accounts)
+}
+
+// Checks each signer key in `new_keys` against the account in the same
+// position, counting from the account after the config account, and returns
+// the number of signer keys. If `current_keys` is given and not empty, each
+// signer must also be one of its stored signers.
+pub(crate) fn check_signers_in_order<A: AccountView>(
+    new_keys: &ConfigKeysRef,
+    accounts: &[A],
+    current_keys: Option<&ConfigKeysRef>,
+) -> Result<usize, StoreError> {
+    let config_account = accounts.first().ok_or(StoreError::MissingConfigAccount)?;
+    let mut counter: usize = 0;
+    for signer in new_keys.signers() {
+        counter = counter.saturating_add(1);
+        if signer != config_account.pubkey() {
+            let signer_account = accounts
+                .get(counter)
+                .ok_or(StoreError::MissingSignerAccount(*signer))?;
+            if !signer_account.is_signer() {
+                return Err(StoreError::MissingSignature(*signer));
+            }
+            if signer_account.pubkey() != signer {
+                return Err(StoreError::SignerMismatch {
+                    index: counter,
+                    expected: *signer,
+                });
+            }
+            // If Config account is already initialized, update signatures must match Config
+            // data.
+            if let Some(current_keys) = current_keys {
+                if !current_keys.is_empty() && !current_keys.signers().any(|key| key == signer) {
+                    return Err(StoreError::UnknownSigner(*signer));
+                }
+            }
+        } else if !config_account.is_signer() {
+            return Err(StoreError::MissingSignature(*signer));
+        }
+    }
+    Ok(counter)
+}
+
+/// Check that `new_keys` contains no duplicate entries.
+pub fn check_unique_keys(new_keys: &ConfigKeysRef) -> Result<(), StoreError> {
+    let unique_keys = new_keys.iter().collect::<BTreeSet<_>>();
+    if unique_keys.len() != new_keys.len() {
+        return Err(StoreError::DuplicateKeys);
+    }
+    Ok(())
+}
+
+/// Check that `new_keys` record at most one signer threshold, between one
+/// and the number of signer keys, and return it.
+pub fn check_threshold(new_keys: &ConfigKeysRef) -> Result<Option<u8>, StoreError> {
+    let mut thresholds = new_keys
+        .iter()
+        .filter(|(_, is_signer)| !*is_signer)
+        .filter_map(|(pubkey, _)| parse_threshold_marker(pubkey));
+    let Some(threshold) = thresholds.next() else {
+        return Ok(None);
+    };
+    if thresholds.next().is_some()
+        || threshold == 0
+        || usize::from(threshold) > new_keys.signers().count()
+    {
+        return Err(StoreError::InvalidThreshold);
+    }
+    Ok(Some(threshold))
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            instruction::{store, store_with_signers},
+            state::{threshold_marker, ConfigKeys, FROZEN_MARKER},
+        },
+    };
+
+    fn account_data(keys: Vec<(Pubkey, bool)>, space: usize) -> Vec<u8> {
+        let mut data = bincode::serialize(&ConfigKeys { keys }).unwrap();
+        data.resize(space, 0);
+        data
+    }
+
+    #[test]
+    fn test_preflight_store() {
+        let config = Pubkey::new_unique();
+        let signer0 = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let keys = vec![(signer0, true), (signer1, true)];
+        let data = account_data(keys.clone(), 128);
+
+        let instruction = store(&config, false, keys.clone(), &42u64);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer1]),
+            Ok(())
+        );
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0]),
+            Err(StoreError::MissingSignature(signer1))
+        );
+
+        // Missing stored signer.
+        let instruction = store(&config, false, vec![(signer0, true)], &42u64);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0]),
+            Err(StoreError::TooFewSigners)
+        );
+
+        // Signer not stored.
+        let signer2 = Pubkey::new_unique();
+        let instruction = store(&config, false, vec![(signer0, true), (signer2, true)], &0u8);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer2]),
+            Err(StoreError::UnknownSigner(signer2))
+        );
+
+        // Duplicate keys.
+        let instruction = store(&config, false, vec![(signer0, true), (signer0, true)], &0u8);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0]),
+            Err(StoreError::DuplicateKeys)
+        );
+
+        // Data too large.
+        let instruction = store(&config, false, keys.clone(), &vec![0u8; 64]);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer1]),
+            Err(StoreError::InstructionDataTooLarge)
+        );
+
+        // Readonly account.
+        let mut instruction = store(&config, false, keys.clone(), &42u64);
+        instruction.accounts[0].is_writable = false;
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer1]),
+            Err(StoreError::ReadonlyDataModified)
+        );
+
+        // Frozen account.
+        let mut frozen_keys = keys;
+        frozen_keys.push((FROZEN_MARKER, true));
+        let frozen_data = account_data(frozen_keys.clone(), 256);
+        let instruction = store(&config, false, frozen_keys, &42u64);
+        assert_eq!(
+            preflight_store(&frozen_data, &instruction, &[signer0, signer1]),
+            Err(StoreError::AccountFrozen)
+        );
+
+        // Uninitialized account requires the config account to sign.
+        let data = vec![0; 128];
+        let instruction = store(&config, false, vec![], &42u64);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[]),
+            Err(StoreError::MissingSignature(config))
+        );
+        let instruction = store(&config, true, vec![], &42u64);
+        assert_eq!(preflight_store(&data, &instruction, &[config]), Ok(()));
+    }
+
+    #[test]
+    fn test_preflight_store_with_threshold() {
+        let config = Pubkey::new_unique();
+        let signer0 = Pubkey::new_unique();
+        let signer1 = Pubkey::new_unique();
+        let signer2 = Pubkey::new_unique();
+        let keys = vec![
+            (signer0, true),
+            (signer1, true),
+            (signer2, true),
+            (threshold_marker(2), false),
+        ];
+        let data = account_data(keys.clone(), 256);
+
+        let instruction =
+            store_with_signers(&config, false, keys.clone(), &42u64, &[signer2, signer0]);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer2]),
+            Ok(())
+        );
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0]),
+            Err(StoreError::TooFewSigners)
+        );
+
+        let mut new_keys = keys;
+        new_keys[3] = (threshold_marker(1), false);
+        let instruction = store_with_signers(&config, false, new_keys, &42u64, &[signer0, signer1]);
+        assert_eq!(
+            preflight_store(&data, &instruction, &[signer0, signer1]),
+            Err(StoreError::SignersChanged)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Validator info, published by validators in config accounts.
+//!
+//! A validator info account lists [`ID`] as a non-signer key, followed by
+//! the validator's identity as the only signer, and stores a
+//! [`ValidatorInfo`] holding a JSON object with the validator's details.
+
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+#[cfg(feature = "bincode")]
+use {
+    crate::{
+        instruction::{create_account_with_space, store as store_config},
+        state::ConfigAccount,
+    },
+    solana_instruction::Instruction,
+};
+use {
+    crate::{space::ConfigSpace, state::PayloadError},
+    alloc::{string::String, vec::Vec},
+    solana_pubkey::Pubkey,
+};
+
+/// The key identifying validator info config accounts.
+pub const ID: Pubkey = solana_pubkey::pubkey!("Va1idator1nfo111111111111111111111111111111");
+
+/// The maximum serialized size of a [`ValidatorInfo`], in bytes.
+pub const MAX_VALIDATOR_INFO: u64 = 576;
+
+/// The payload of a validator info account.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ValidatorInfo {
+    /// A JSON object with the validator's details, such as `name`,
+    /// `website`, `details`, `iconUrl` and `keybaseUsername`.
+    pub info: String,
+}
+
+impl ValidatorInfo {
+    /// The serialized size of the payload: a `u64` length followed by the
+    /// JSON bytes.
+    pub fn serialized_size(&self) -> u64 {
+        (self.info.len() as u64).saturating_add(8)
+    }
+
+    /// Check that the payload fits in a validator info account.
+    pub fn check_size(&self) -> Result<(), PayloadError> {
+        if self.serialized_size() > MAX_VALIDATOR_INFO {
+            return Err(PayloadError::PayloadTooLarge);
+        }
+        Ok(())
+    }
+}
+
+// Encoded as under bincode, with a `u64` length rather than borsh's `u32`.
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for ValidatorInfo {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        (self.info.len() as u64).serialize(writer)?;
+        writer.write_all(self.info.as_bytes())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for ValidatorInfo {
+    fn deserialize_reader<R: borsh::io::Read>(reader: &mut R) -> borsh::io::Result<Self> {
+        let len = u64::deserialize_reader(reader)?;
+        let mut info = Vec::new();
+        borsh::io::Read::read_to_end(&mut borsh::io::Read::take(reader, len), &mut info)?;
+        if info.len() as u64 != len {
+            return Err(borsh::io::ErrorKind::UnexpectedEof.into());
+        }
+        let info = String::from_utf8(info).map_err(|_| {
+            borsh::io::Error::new(borsh::io::ErrorKind::InvalidData, "invalid UTF-8")
+        })?;
+        Ok(Self { info })
+    }
+}
+
+/// The keys of the validator info account for `identity`.
+pub fn keys(identity: &Pubkey) -> Vec<(Pubkey, bool)> {
+    alloc::vec![(ID, false), (*identity, true)]
+}
+
+/// Get the validator identity from validator info keys, if they have the
+/// canonical layout.
+pub fn identity(keys: &[(Pubkey, bool)]) -> Option<&Pubkey> {
+    match keys {
+        [(id, false), (identity, true)] if *id == ID => Some(identity),
+        _ => None,
+    }
+}
+
+/// The account space needed for a validator info account.
+pub fn space() -> u64 {
+    // Two keys and the largest validator info always fit.
+    ConfigSpace::new(2, MAX_VALIDATOR_INFO).unwrap().space()
+}
+
+/// Parse validator info account data into the validator identity and its
+/// info.
+#[cfg(feature = "bincode")]
+pub fn parse(account_data: &[u8]) -> Result<(Pubkey, ValidatorInfo), PayloadError> {
+    let ConfigAccount { keys, data, .. } =
+        ConfigAccount::<ValidatorInfo>::from_bytes(account_data)?;
+    let identity = *identity(&keys.keys).ok_or(PayloadError::InvalidKeys)?;
+    data.check_size()?;
+    Ok((identity, data))
+}
+
+/// Create a validator info account, sized for the largest validator info
+///
+/// The account must then be initialized with [`store`], signed by the new
+/// account.
+#[cfg(feature = "bincode")]
+pub fn create_account(
+    from_account_pubkey: &Pubkey,
+    config_account_pubkey: &Pubkey,
+    identity: &Pubkey,
+    lamports: u64,
+) -> Vec<Instruction> {
+    // Two keys and the largest validator info always fit.
+    let space = ConfigSpace::for_keys(&keys(identity), MAX_VALIDATOR_INFO).unwrap();
+    create_account_with_space::<ValidatorInfo>(
+        from_account_pubkey,
+        config_account_pubkey,
+        lamports,
+        &space,
+    )
+}
+
+/// Publish validator info for `identity`
+///
+/// The config account must sign the first `store` into a new account, and
+/// the identity must sign every `store`.
+#[cfg(feature = "bincode")]
+pub fn store(
+    config_account_pubkey: &Pubkey,
+    is_config_signer: bool,
+    identity: &Pubkey,
+    info: &ValidatorInfo,
+) -> Result<Instruction, PayloadError> {
+    info.check_size()?;
+    Ok(store_config(
+        config_account_pubkey,
+        is_config_signer,
+        keys(identity),
+        info,
+    ))
+}
+
+#[cfg(all(test, feature = "bincode"))]
+mod tests {
+    use {super::*, crate::state::ConfigKeys, solana_instruction::AccountMeta};
+
+    #[test]
+    fn test_validator_info() {
+        assert_eq!(space(), 643);
+
+        let config = Pubkey::new_unique();
+        let identity = Pubkey::new_unique();
+        let info = ValidatorInfo {
+            info: r#"{"name":"Validator"}"#.to_string(),
+        };
+        let instruction = store(&config, true, &identity, &info).unwrap();
+        assert_eq!(
+            instruction.accounts,
+            vec![
+                AccountMeta::new(config, true),
+                AccountMeta::new(identity, true),
+            ]
+        );
+
+        let mut account_data = instruction.data;
+        account_data.resize(space() as usize, 0);
+        assert_eq!(parse(&account_data), Ok((identity, info)));
+
+        // The largest validator info fills the account.
+        let info = ValidatorInfo {
+            info: "x".repeat(MAX_VALIDATOR_INFO as usize - 8),
+        };
+        let instruction = store(&config, false, &identity, &info).unwrap();
+        assert_eq!(instruction.data.len() as u64, space());
+
+        let info = ValidatorInfo {
+            info: "x".repeat(MAX_VALIDATOR_INFO as usize - 7),
+        };
+        assert_eq!(
+            store(&config, false, &identity, &info),
+            Err(PayloadError::PayloadTooLarge)
+        );
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_validator_info_borsh() {
+        let keys = ConfigKeys {
+            keys: keys(&Pubkey::new_unique()),
+        };
+        for info in ["", r#"{"name":"Validator"}"#, "validator \u{1F680}"] {
+            let info = ValidatorInfo {
+                info: info.to_string(),
+            };
+            let bytes = borsh::to_vec(&(&keys, &info)).unwrap();
+            assert_eq!(bytes, bincode::serialize(&(&keys, &info)).unwrap());
+            assert_eq!(
+                borsh::from_slice::<(ConfigKeys, ValidatorInfo)>(&bytes).unwrap(),
+                (keys.clone(), info)
+            );
+        }
+
+        // Truncated and invalid UTF-8 info.
+        for bytes in [
+            vec![4, 0, 0, 0, 0, 0, 0, 0, b'a'],
+            vec![1, 0, 0, 0, 0, 0, 0, 0, 0xFF],
+        ] {
+            assert!(bincode::deserialize::<ValidatorInfo>(&bytes).is_err());
+            assert!(borsh::from_slice::<ValidatorInfo>(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_keys() {
+        let identity = Pubkey::new_unique();
+        let info = ValidatorInfo::default();
+        for keys in [
+            vec![(ID, false)],
+            vec![(ID, true), (identity, true)],
+            vec![(ID, false), (identity, false)],
+            vec![(identity, false), (ID, true)],
+            vec![(ID, false), (identity, true), (Pubkey::new_unique(), true)],
+        ] {
+            let account_data = bincode::serialize(&(ConfigKeys { keys }, &info)).unwrap();
+            assert_eq!(parse(&account_data), Err(PayloadError::InvalidKeys));
+        }
+
+        assert_eq!(parse(&[]), Err(PayloadError::InvalidAccountData));
+    }
+}
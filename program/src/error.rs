@@ -7,12 +7,84 @@ use {
 };
 
 /// Errors that can be returned by the Config program.
+///
+/// Apart from `ReadonlyDataModified` and the errors of instructions the
+/// original builtin never had, these are only returned when the program is
+/// built with the `detailed-errors` feature. Otherwise, the program returns
+/// the same generic `ProgramError` as the builtin.
 #[repr(u32)]
 #[derive(Error, Clone, Debug, Eq, PartialEq, FromPrimitive)]
 pub enum ConfigError {
     /// Instruction modified data of a read-only account.
     #[error("Instruction modified data of a read-only account")]
     ReadonlyDataModified,
+    /// Config data does not match the expected hash.
+    #[error("Config data does not match the expected hash")]
+    DataHashMismatch,
+    /// Config account is not owned by the config program.
+    #[error("Config account is not owned by the config program")]
+    InvalidAccountOwner,
+    /// Instruction data could not be deserialized.
+    #[error("Instruction data could not be deserialized")]
+    InvalidInstruction,
+    /// Instruction contains invalid config keys.
+    #[error("Instruction contains invalid config keys")]
+    InvalidConfigKeys,
+    /// Config account data could not be deserialized.
+    #[error("Config account data could not be deserialized")]
+    InvalidAccountState,
+    /// A required signer did not sign.
+    #[error("A required signer did not sign")]
+    MissingSignature,
+    /// Signer account does not match the config keys.
+    #[error("Signer account does not match the config keys")]
+    SignerMismatch,
+    /// Signer is not in the stored signer list.
+    #[error("Signer is not in the stored signer list")]
+    UnknownSigner,
+    /// Too few of the stored signers signed.
+    #[error("Too few of the stored signers signed")]
+    TooFewSigners,
+    /// New config contains duplicate keys.
+    #[error("New config contains duplicate keys")]
+    DuplicateKeys,
+    /// Instruction data is too large for the config account.
+    #[error("Instruction data is too large for the config account")]
+    InstructionDataTooLarge,
+    /// New config contains an invalid signer threshold.
+    #[error("New config contains an invalid signer threshold")]
+    InvalidThreshold,
+    /// Signers and threshold must match the stored config.
+    #[error("Signers and threshold must match the stored config")]
+    SignersChanged,
+    /// Lamports cannot be moved to the config account itself.
+    #[error("Lamports cannot be moved to the config account itself")]
+    InvalidDestination,
+    /// New length would truncate the stored keys.
+    #[error("New length would truncate the stored keys")]
+    KeysTruncated,
+    /// Write exceeds the config account data.
+    #[error("Write exceeds the config account data")]
+    WriteOutOfBounds,
+    /// New keys would push non-zero config data off the end of the account.
+    #[error("New keys would push non-zero config data off the end of the account")]
+    DataTruncated,
+    /// Config account is frozen.
+    #[error("Config account is frozen")]
+    AccountFrozen,
+}
+
+impl ConfigError {
+    /// Returns this error if the program is built with the `detailed-errors`
+    /// feature, or `legacy`, the error returned by the original builtin,
+    /// otherwise.
+    pub fn or_legacy(self, legacy: ProgramError) -> ProgramError {
+        if cfg!(feature = "detailed-errors") {
+            self.into()
+        } else {
+            legacy
+        }
+    }
 }
 
 impl From<ConfigError> for ProgramError {
@@ -25,6 +97,26 @@ impl ToStr for ConfigError {
     fn to_str(&self) -> &'static str {
         match self {
             Self::ReadonlyDataModified => "Instruction modified data of a read-only account",
+            Self::DataHashMismatch => "Config data does not match the expected hash",
+            Self::InvalidAccountOwner => "Config account is not owned by the config program",
+            Self::InvalidInstruction => "Instruction data could not be deserialized",
+            Self::InvalidConfigKeys => "Instruction contains invalid config keys",
+            Self::InvalidAccountState => "Config account data could not be deserialized",
+            Self::MissingSignature => "A required signer did not sign",
+            Self::SignerMismatch => "Signer account does not match the config keys",
+            Self::UnknownSigner => "Signer is not in the stored signer list",
+            Self::TooFewSigners => "Too few of the stored signers signed",
+            Self::DuplicateKeys => "New config contains duplicate keys",
+            Self::InstructionDataTooLarge => "Instruction data is too large for the config account",
+            Self::InvalidThreshold => "New config contains an invalid signer threshold",
+            Self::SignersChanged => "Signers and threshold must match the stored config",
+            Self::InvalidDestination => "Lamports cannot be moved to the config account itself",
+            Self::KeysTruncated => "New length would truncate the stored keys",
+            Self::WriteOutOfBounds => "Write exceeds the config account data",
+            Self::DataTruncated => {
+                "New keys would push non-zero config data off the end of the account"
+            }
+            Self::AccountFrozen => "Config account is frozen",
         }
     }
 }
@@ -2,54 +2,33 @@
 
 use {
     crate::error::ConfigError,
-    solana_account_info::AccountInfo,
-    solana_config_interface::state::ConfigKeys,
+    solana_account_info::{next_account_info, AccountInfo},
+    solana_config_interface::{
+        instruction::EXTENSION_PREFIX,
+        state::{ConfigKeys, ConfigKeysRef, FROZEN_MARKER},
+        validate::{
+            self, check_store, check_threshold, check_unique_keys, parse_store_keys, StoreError,
+        },
+    },
+    solana_hash::Hash,
     solana_msg::msg,
     solana_program_error::{ProgramError, ProgramResult},
     solana_pubkey::Pubkey,
-    std::collections::BTreeSet,
+    solana_sysvar::{rent::Rent, Sysvar},
 };
 
-// Maximum input buffer length that can be deserialized.
-// See `solana_sdk::packet::PACKET_DATA_SIZE`.
-const MAX_INPUT_LEN: usize = 1232;
-// Maximum vector length for a `ConfigKeys` struct's `keys` list.
-// See comments below for `safe_deserialize_config_keys`.
-//
-// Take the maximum input length and subtract (up to) 3 bytes for the
-// `ShortU16`, then divide that by the size of a `(Pubkey, bool)` entry.
-const MAX_VECTOR_LEN: usize = (MAX_INPUT_LEN - 3) / (32 + 1);
-
-// [Core BPF]: The original Config builtin leverages the
-// `solana_bincode::limited_deserialize` method to cap the length of
-// the input buffer at `MAX_INPUT_LEN` (1232). As a result, any input buffer
-// larger than `MAX_INPUT_LEN` will abort deserialization and return
-// `InstructionError::InvalidInstructionData`.
-//
-// Howevever, since `ConfigKeys` contains a vector of `(Pubkey, bool)`, the
-// `limited_deserialize` method will still read the vector's length and attempt
-// to allocate a vector of the designated size. For extremely large length
-// values, this can cause the initial allocation of a large vector to exhuast
-// the BPF program's heap before deserialization can proceed.
-//
-// To mitigate this memory issue, the BPF version of the Config program has
-// been designed to "peek" the length value, and ensure it cannot allocate a
-// vector that would otherwise violate the input buffer length restriction.
-// Since a `ShortU16` value for `MAX_VECTOR_LEN` fits in just one byte, we can
-// simply peek the first byte before attempting deserialization.
-fn safe_deserialize_config_keys(input: &[u8]) -> Result<ConfigKeys, ProgramError> {
-    match input.first() {
-        Some(first_byte) if *first_byte as usize <= MAX_VECTOR_LEN => {
-            solana_bincode::limited_deserialize::<ConfigKeys>(input, MAX_INPUT_LEN as u64)
-                .map_err(|_| ProgramError::InvalidInstructionData)
-        }
-        _ => Err(ProgramError::InvalidInstructionData),
-    }
+// [Core BPF]: `store`, `CompareAndStore` and `SetKeys` all read their new keys
+// through this helper. `validate::parse_store_keys` rejects a key count above
+// `MAX_STORE_KEYS` by peeking its first byte, and then reads the keys in place
+// without allocating, so a large encoded length can't exhaust the BPF
+// program's heap. See its docs for how this compares to the original builtin.
+fn safe_deserialize_config_keys(input: &[u8]) -> Result<ConfigKeysRef<'_>, ProgramError> {
+    parse_store_keys(input).map_err(store_error)
 }
 
-// [Core BPF]: Similar to `safe_deserialize_config_keys`, this helper serves to
-// avoid over-allocations of memory, especially when the trailing data is
-// invalid.
+// [Core BPF]: Unlike the new keys in instruction data, the stored keys are
+// deserialized into an owned `ConfigKeys`, so this helper serves to avoid
+// over-allocations of memory when the account data is malformed.
 //
 // Consider a case where an account is malformed, and the `ShortU16` vector
 // length actually stores a value larger than the buffer itself. The original
@@ -58,128 +37,421 @@ fn safe_deserialize_config_keys(input: &[u8]) -> Result<ConfigKeys, ProgramError
 // error. However, the BPF version will panic on OOM before it can successfully
 // return `ProgramError::InvalidAccountData`.
 //
-// This helper's purpose is solely to catch malformed `ConfigKeys` data before
-// a memory allocation panic can occur, to ensure maximum backwards
-// compatibility with the original builtin.
+// This helper checks the length against the buffer before bincode allocates
+// the vector, to ensure maximum backwards compatibility with the original
+// builtin.
 fn safe_deserialize_config_keys_from_state(input: &[u8]) -> Result<ConfigKeys, ProgramError> {
-    let (vector_len, offset) = solana_short_vec::decode_shortu16_len(input)
-        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let (vector_len, offset) = solana_short_vec::decode_shortu16_len(input).map_err(|_| {
+        ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData)
+    })?;
     if input[offset..].len() / (32 + 1) < vector_len {
-        Err(ProgramError::InvalidAccountData)
+        Err(ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData))
     } else {
         bincode::deserialize(input).map_err(|err| {
             msg!("Unable to deserialize config account: {}", err);
-            ProgramError::InvalidAccountData
+            ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData)
         })
     }
 }
 
-/// Config program processor.
-pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
-    let key_list = safe_deserialize_config_keys(input)?;
+// Maps a failed `store` check to the error returned by the program.
+fn store_error(error: StoreError) -> ProgramError {
+    msg!("{}", error);
+    match error {
+        StoreError::IncorrectProgramId => ProgramError::IncorrectProgramId,
+        StoreError::NotStore => {
+            ConfigError::InvalidInstruction.or_legacy(ProgramError::InvalidInstructionData)
+        }
+        StoreError::InvalidConfigKeys => {
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData)
+        }
+        StoreError::MissingConfigAccount => ProgramError::NotEnoughAccountKeys,
+        StoreError::InvalidAccountState => {
+            ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData)
+        }
+        // Nobody can sign for the frozen marker, so the builtin rejects the
+        // store as unsigned.
+        StoreError::AccountFrozen => {
+            ConfigError::AccountFrozen.or_legacy(ProgramError::MissingRequiredSignature)
+        }
+        StoreError::MissingSignerAccount(_) | StoreError::MissingSignature(_) => {
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature)
+        }
+        StoreError::SignerMismatch { .. } => {
+            ConfigError::SignerMismatch.or_legacy(ProgramError::MissingRequiredSignature)
+        }
+        StoreError::UnknownSigner(_) => {
+            ConfigError::UnknownSigner.or_legacy(ProgramError::MissingRequiredSignature)
+        }
+        StoreError::TooFewSigners => {
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature)
+        }
+        StoreError::InvalidThreshold => {
+            ConfigError::InvalidThreshold.or_legacy(ProgramError::InvalidArgument)
+        }
+        StoreError::SignersChanged => {
+            ConfigError::SignersChanged.or_legacy(ProgramError::InvalidArgument)
+        }
+        StoreError::DuplicateKeys => {
+            ConfigError::DuplicateKeys.or_legacy(ProgramError::InvalidArgument)
+        }
+        StoreError::InstructionDataTooLarge => {
+            ConfigError::InstructionDataTooLarge.or_legacy(ProgramError::InvalidInstructionData)
+        }
+        StoreError::ReadonlyDataModified => ConfigError::ReadonlyDataModified.into(),
+    }
+}
 
-    let config_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+// Checks that the signers stored in the config account's `ConfigKeys` have
+// signed, as required by every instruction other than `store`.
+fn check_stored_signers<'a>(
+    config_account: &AccountInfo<'a>,
+    stored_data: &ConfigKeys,
+    signer_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let stored_signers = stored_data
+        .keys
+        .iter()
+        .filter(|(_, is_signer)| *is_signer)
+        .map(|(pubkey, _)| pubkey);
+    validate::check_stored_signers(
+        config_account,
+        stored_signers,
+        stored_data.threshold(),
+        signer_accounts,
+    )
+    .map_err(store_error)
+}
 
+// Loads the config account for an extension instruction, checking that it is
+// owned by the program and writable, and returns its stored keys.
+fn load_config_account(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<ConfigKeys, ProgramError> {
     if config_account.owner != program_id {
         msg!("Config account is not owned by the config program");
-        return Err(ProgramError::InvalidAccountOwner);
+        return Err(ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner));
+    }
+    if !config_account.is_writable {
+        return Err(ConfigError::ReadonlyDataModified.into());
     }
+    let stored_data = safe_deserialize_config_keys_from_state(&config_account.try_borrow_data()?)?;
+    if stored_data.is_frozen() {
+        msg!("Config account is frozen");
+        return Err(ConfigError::AccountFrozen.or_legacy(ProgramError::Immutable));
+    }
+    Ok(stored_data)
+}
 
-    let current_data = safe_deserialize_config_keys_from_state(&config_account.try_borrow_data()?)?;
+fn process_close(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;
+    let destination_account = next_account_info(accounts_iter)?;
 
-    let current_signer_keys: Vec<Pubkey> = current_data
-        .keys
-        .iter()
-        .filter(|(_, is_signer)| *is_signer)
-        .map(|(pubkey, _)| *pubkey)
-        .collect();
-
-    if current_signer_keys.is_empty() {
-        // Config account keypair must be a signer on account initialization,
-        // or when no signers specified in Config data.
-        if !config_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-    }
-
-    let mut counter: usize = 0;
-    for (signer, _) in key_list.keys.iter().filter(|(_, is_signer)| *is_signer) {
-        counter = counter.saturating_add(1);
-        if signer != config_account.key {
-            let signer_account = accounts.get(counter).ok_or_else(|| {
-                msg!("account {:?} is not in account list", signer);
-                ProgramError::MissingRequiredSignature
-            })?;
-            if !signer_account.is_signer {
-                msg!("account {:?} signer_key().is_none()", signer);
-                return Err(ProgramError::MissingRequiredSignature);
-            }
-            if signer_account.key != signer {
-                msg!(
-                    "account[{:?}].signer_key() does not match Config data)",
-                    counter.saturating_add(1)
-                );
-                return Err(ProgramError::MissingRequiredSignature);
-            }
-            // If Config account is already initialized, update signatures must match Config
-            // data.
-            if !current_data.keys.is_empty()
-                && !current_signer_keys.iter().any(|pubkey| pubkey == signer)
-            {
-                msg!("account {:?} is not in stored signer list", signer);
-                return Err(ProgramError::MissingRequiredSignature);
-            }
-        } else if !config_account.is_signer {
-            msg!("account[0].signer_key().is_none()");
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-    }
-
-    // Dedupe signers.
-    let total_new_keys = key_list.keys.len();
-    let unique_new_keys = key_list.keys.into_iter().collect::<BTreeSet<_>>();
-    if unique_new_keys.len() != total_new_keys {
-        msg!("new config contains duplicate keys");
-        return Err(ProgramError::InvalidArgument);
-    }
-
-    // Check for Config data signers not present in incoming account update.
-    if current_signer_keys.len() > counter {
+    let stored_data = load_config_account(program_id, config_account)?;
+    check_stored_signers(config_account, &stored_data, accounts_iter.as_slice())?;
+
+    if config_account.key == destination_account.key {
+        msg!("Destination cannot be the config account");
+        return Err(ConfigError::InvalidDestination.or_legacy(ProgramError::InvalidArgument));
+    }
+
+    let destination_lamports = destination_account
+        .lamports()
+        .checked_add(config_account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **destination_account.try_borrow_mut_lamports()? = destination_lamports;
+    **config_account.try_borrow_mut_lamports()? = 0;
+
+    config_account.try_borrow_mut_data()?.fill(0);
+    config_account.resize(0)?;
+    config_account.assign(&solana_sdk_ids::system_program::id());
+
+    Ok(())
+}
+
+fn process_resize(program_id: &Pubkey, accounts: &[AccountInfo], new_len: u64) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;
+    let funding_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    let stored_data = load_config_account(program_id, config_account)?;
+    check_stored_signers(config_account, &stored_data, accounts_iter.as_slice())?;
+
+    if config_account.key == funding_account.key {
+        msg!("Funding account cannot be the config account");
+        return Err(ConfigError::InvalidDestination.or_legacy(ProgramError::InvalidArgument));
+    }
+
+    let new_len = usize::try_from(new_len).map_err(|_| ProgramError::InvalidArgument)?;
+    let keys_len = bincode::serialized_size(&stored_data)
+        .map_err(|_| ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData))?
+        as usize;
+    if new_len < keys_len {
+        msg!("New length would truncate the stored keys");
+        return Err(ConfigError::KeysTruncated.or_legacy(ProgramError::InvalidArgument));
+    }
+
+    let rent = Rent::get()?;
+    let rent_exempt_lamports = rent.minimum_balance(new_len);
+    let current_lamports = config_account.lamports();
+    let data_len = config_account.data_len();
+    if rent_exempt_lamports > current_lamports {
+        solana_cpi::invoke(
+            &solana_system_interface::instruction::transfer(
+                funding_account.key,
+                config_account.key,
+                rent_exempt_lamports.saturating_sub(current_lamports),
+            ),
+            &[
+                funding_account.clone(),
+                config_account.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    } else if new_len < data_len {
+        // Only the rent freed by shrinking is refunded, so lamports deposited
+        // beyond the rent-exempt minimum stay in the account.
+        let refund = rent
+            .minimum_balance(data_len)
+            .saturating_sub(rent_exempt_lamports)
+            .min(current_lamports.saturating_sub(rent_exempt_lamports));
+        let funding_lamports = funding_account
+            .lamports()
+            .checked_add(refund)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **funding_account.try_borrow_mut_lamports()? = funding_lamports;
+        **config_account.try_borrow_mut_lamports()? = current_lamports.saturating_sub(refund);
+    }
+
+    config_account.resize(new_len)
+}
+
+fn process_write(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    offset: u32,
+    data: &[u8],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;
+
+    let stored_data = load_config_account(program_id, config_account)?;
+    check_stored_signers(config_account, &stored_data, accounts_iter.as_slice())?;
+
+    let keys_len = bincode::serialized_size(&stored_data)
+        .map_err(|_| ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData))?
+        as usize;
+    let start = keys_len
+        .checked_add(offset as usize)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    let end = start
+        .checked_add(data.len())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if config_account.data_len() < end {
+        msg!("Write exceeds config account data");
+        return Err(ConfigError::WriteOutOfBounds.or_legacy(ProgramError::AccountDataTooSmall));
+    }
+
+    config_account.try_borrow_mut_data()?[start..end].copy_from_slice(data);
+
+    Ok(())
+}
+
+fn process_compare_and_store(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    expected_hash: &Hash,
+    input: &[u8],
+) -> ProgramResult {
+    let config_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if config_account.owner != program_id {
+        msg!("Config account is not owned by the config program");
+        return Err(ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner));
+    }
+
+    let data_hash = ConfigKeysRef::new(&config_account.try_borrow_data()?)
+        .map_err(|_| ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData))?
+        .data_hash();
+    if data_hash != *expected_hash {
         msg!(
-            "too few signers: {:?}; expected: {:?}",
-            counter,
-            current_signer_keys.len()
+            "Config data hash {} does not match {}",
+            data_hash,
+            expected_hash
         );
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    if config_account.data_len() < input.len() {
-        msg!("Instruction data too large");
-        return Err(ProgramError::InvalidInstructionData);
-    }
-
-    // [Core BPF]:
-    // When a builtin program attempts to write to an executable or read-only
-    // account, it will be immediately rejected by the `TransactionContext`.
-    // However, BPF programs do not query the `TransactionContext` for the
-    // ability to perform a write. Instead, they perform writes at-will, and
-    // the loader will inspect the serialized account memory region for any
-    // account update violations _after_ the VM has completed execution.
-    //
-    // The loader's inspection will catch any unauthorized modifications,
-    // however, when the exact same data is written to the account, thus
-    // rendering the serialized account state unchanged, the program succeeds.
-    //
-    // In order to maximize backwards compatibility between the BPF version and
-    // its original builtin, we add this check from `TransactionContext` to the
-    // program directly, to throw even when the data being written is the same
-    // same as what's currently in the account.
-    //
-    // Since the account can never be executable and also owned by the config
-    // program, we'll just focus on readonly.
-    if !config_account.is_writable {
-        return Err(ConfigError::ReadonlyDataModified.into());
+        return Err(ConfigError::DataHashMismatch.into());
+    }
+
+    process_store(program_id, accounts, input)
+}
+
+fn process_set_keys(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    // Hold the new keys to the same rules as `store`, so the account can
+    // still be updated by `store` afterwards.
+    let new_keys = safe_deserialize_config_keys(input)?;
+    let new_keys_bytes = &input[..new_keys.serialized_size()];
+
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;
+
+    let stored_data = load_config_account(program_id, config_account)?;
+    check_stored_signers(config_account, &stored_data, accounts_iter.as_slice())?;
+
+    check_threshold(&new_keys).map_err(store_error)?;
+    check_unique_keys(&new_keys).map_err(store_error)?;
+
+    replace_keys(config_account, &stored_data, new_keys_bytes)
+}
+
+fn process_freeze(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let config_account = next_account_info(accounts_iter)?;
+
+    let stored_data = load_config_account(program_id, config_account)?;
+    check_stored_signers(config_account, &stored_data, accounts_iter.as_slice())?;
+
+    let mut frozen_keys = stored_data.clone();
+    frozen_keys.keys.push((FROZEN_MARKER, true));
+    let frozen_keys_bytes = bincode::serialize(&frozen_keys).map_err(|_| {
+        ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData)
+    })?;
+
+    replace_keys(config_account, &stored_data, &frozen_keys_bytes)
+}
+
+// Replaces the stored keys with `new_keys_bytes`, moving the config data that
+// follows them so that it is preserved.
+fn replace_keys(
+    config_account: &AccountInfo,
+    stored_data: &ConfigKeys,
+    new_keys_bytes: &[u8],
+) -> ProgramResult {
+    let old_keys_len = bincode::serialized_size(stored_data)
+        .map_err(|_| ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData))?
+        as usize;
+    let new_keys_len = new_keys_bytes.len();
+
+    let mut data = config_account.try_borrow_mut_data()?;
+    let data_len = data.len();
+    if new_keys_len > data_len {
+        msg!("New keys are larger than the config account");
+        return Err(ConfigError::DataTruncated.or_legacy(ProgramError::AccountDataTooSmall));
     }
+    if new_keys_len > old_keys_len {
+        // Move the data right, dropping bytes off the end of the account,
+        // which must be zero.
+        let shift = new_keys_len.saturating_sub(old_keys_len);
+        let data_end = data_len.saturating_sub(shift);
+        if data[data_end..].iter().any(|byte| *byte != 0) {
+            msg!("New keys would truncate the config data");
+            return Err(ConfigError::DataTruncated.or_legacy(ProgramError::AccountDataTooSmall));
+        }
+        data.copy_within(old_keys_len..data_end, new_keys_len);
+    } else {
+        // Move the data left, zeroing the bytes freed at the end of the
+        // account.
+        let shift = old_keys_len.saturating_sub(new_keys_len);
+        data.copy_within(old_keys_len.., new_keys_len);
+        data[data_len.saturating_sub(shift)..].fill(0);
+    }
+    data[..new_keys_len].copy_from_slice(new_keys_bytes);
+
+    Ok(())
+}
+
+fn invalid_instruction() -> ProgramError {
+    ConfigError::InvalidInstruction.or_legacy(ProgramError::InvalidInstructionData)
+}
+
+// Splits `N` bytes off the front of extension instruction data.
+fn split_array<const N: usize>(input: &[u8]) -> Result<([u8; N], &[u8]), ProgramError> {
+    input
+        .split_first_chunk::<N>()
+        .map(|(array, rest)| (*array, rest))
+        .ok_or_else(invalid_instruction)
+}
+
+// Splits a bincode-encoded byte vector off the front of extension instruction
+// data, checking its length against the input rather than allocating it.
+fn split_bytes(input: &[u8]) -> Result<(&[u8], &[u8]), ProgramError> {
+    let (len, input) = split_array::<8>(input)?;
+    match usize::try_from(u64::from_le_bytes(len)) {
+        Ok(len) if len <= input.len() => Ok(input.split_at(len)),
+        _ => Err(invalid_instruction()),
+    }
+}
+
+// [Core BPF]: Extension instructions are decoded by hand, following the
+// bincode layout of `ConfigInstruction`, rather than with
+// `limited_deserialize`. Deserializing a vector allocates as many elements as
+// its encoded length declares before checking that the input holds them, so a
+// large length exhausts the BPF program's heap instead of returning an error.
+// Variable-length fields are instead checked against the input and read in
+// place.
+fn process_extension(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    let (variant, input) = split_array::<4>(input)?;
+    // Variants are numbered in declaration order.
+    match u32::from_le_bytes(variant) {
+        // `Close`
+        0 => process_close(program_id, accounts),
+        // `Resize { new_len: u64 }`
+        1 => {
+            let (new_len, _) = split_array::<8>(input)?;
+            process_resize(program_id, accounts, u64::from_le_bytes(new_len))
+        }
+        // `Write { offset: u32, data: Vec<u8> }`
+        2 => {
+            let (offset, input) = split_array::<4>(input)?;
+            let (data, _) = split_bytes(input)?;
+            process_write(program_id, accounts, u32::from_le_bytes(offset), data)
+        }
+        // `CompareAndStore { expected_hash: Hash, input: Vec<u8> }`
+        3 => {
+            let (expected_hash, input) = split_array::<32>(input)?;
+            let (input, _) = split_bytes(input)?;
+            process_compare_and_store(
+                program_id,
+                accounts,
+                &Hash::new_from_array(expected_hash),
+                input,
+            )
+        }
+        // `SetKeys { keys: ConfigKeys }`
+        4 => process_set_keys(program_id, accounts, input),
+        // `Freeze`
+        5 => process_freeze(program_id, accounts),
+        _ => Err(invalid_instruction()),
+    }
+}
+
+/// Config program processor.
+pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    match input.split_first() {
+        Some((&EXTENSION_PREFIX, input)) => process_extension(program_id, accounts, input),
+        _ => process_store(program_id, accounts, input),
+    }
+}
+
+fn process_store(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    let key_list = safe_deserialize_config_keys(input)?;
+
+    let config_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if config_account.owner != program_id {
+        msg!("Config account is not owned by the config program");
+        return Err(ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner));
+    }
+
+    check_store(
+        &key_list,
+        input.len(),
+        &config_account.try_borrow_data()?,
+        accounts,
+    )
+    .map_err(store_error)?;
 
     config_account.try_borrow_mut_data()?[..input.len()].copy_from_slice(input);
 
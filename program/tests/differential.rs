@@ -0,0 +1,535 @@
+//! Differential tests between the current build and the regression program
+//! in `fuzz/program-mb.so`, built from `program@3.0.0`.
+//!
+//! Both programs run the same generated `store` instructions, account states
+//! and signer combinations, and must agree on the result and the resulting
+//! accounts. The current build must also stay within a compute unit envelope
+//! of the regression program.
+//!
+//! Data beginning with `EXTENSION_PREFIX`, which only the current build
+//! understands, is never generated. Keys holding a signer threshold marker
+//! or the frozen marker are only generated by `test_differential_markers`,
+//! which checks that the current build departs from the regression program
+//! only where the marker rules apply, and follows a model of those rules where
+//! it does.
+//!
+//! Set `DIFFERENTIAL_SEED` and `DIFFERENTIAL_CASES` to explore other inputs.
+
+#![allow(clippy::arithmetic_side_effects)]
+
+use {
+    mollusk_svm::{
+        program::loader_keys::LOADER_V3,
+        result::{InstructionResult, ProgramResult},
+        Mollusk,
+    },
+    solana_account::Account,
+    solana_config_interface::{
+        instruction::EXTENSION_PREFIX,
+        state::{parse_threshold_marker, threshold_marker, ConfigKeys, FROZEN_MARKER},
+        validate::parse_store_keys,
+    },
+    solana_config_program::error::ConfigError,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+    std::collections::HashSet,
+};
+
+/// The most compute units the current build may use, as a percentage of the
+/// regression program's usage for the same input.
+const COMPUTE_UNIT_ENVELOPE_PERCENT: u64 = 125;
+
+const DEFAULT_SEED: u64 = 0x00C0_FF16_5EED;
+const DEFAULT_CASES: usize = 1_000;
+
+fn setup() -> Mollusk {
+    Mollusk::new(&solana_config_program::id(), "solana_config_program")
+}
+
+fn setup_regression() -> Mollusk {
+    let elf = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/fuzz/program-mb.so"))
+        .expect("failed to read fuzz/program-mb.so");
+    let mut mollusk = Mollusk::default();
+    mollusk.add_program_with_loader_and_elf(&solana_config_program::id(), &LOADER_V3, &elf);
+    mollusk
+}
+
+fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+// A small deterministic generator (SplitMix64), so failures reproduce from
+// the seed alone.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // True with probability `numerator / denominator`.
+    fn chance(&mut self, numerator: usize, denominator: usize) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    fn bytes(&mut self, max_len: usize) -> Vec<u8> {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| self.next_u64() as u8).collect()
+    }
+
+    fn pubkey(&mut self) -> Pubkey {
+        let mut bytes = [0; 32];
+        for chunk in bytes.chunks_exact_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+        Pubkey::new_from_array(bytes)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    // Generated `store` instructions.
+    Store,
+    // Arbitrary instruction data.
+    ArbitraryData,
+    // Generated `store` instructions, with marker keys among the keys.
+    Markers,
+}
+
+// How the current build may differ from the regression program on a case,
+// because of the marker keys it gives meaning to.
+enum Divergence {
+    // The results must agree.
+    None,
+    // The account is frozen, so the current build must fail with
+    // `AccountFrozen` whatever the regression program does.
+    Frozen,
+    // The current build may fail with one of these errors instead.
+    Errors(Vec<ProgramError>),
+    // The stored signer threshold replaces the regression program's signer
+    // checks, so the current build must instead give the result of
+    // `threshold_model`.
+    Threshold(Result<(), ProgramError>),
+}
+
+struct Case {
+    instruction: Instruction,
+    accounts: Vec<(Pubkey, Account)>,
+    divergence: Divergence,
+}
+
+// Keys are drawn from a small pool, starting with the config account, so
+// that stored and new keys overlap often.
+fn key_pool(rng: &mut Rng) -> Vec<Pubkey> {
+    (0..5).map(|_| rng.pubkey()).collect()
+}
+
+fn gen_keys(rng: &mut Rng, pool: &[Pubkey], markers: bool) -> Vec<(Pubkey, bool)> {
+    (0..rng.below(5))
+        .map(|_| {
+            if markers && rng.chance(1, 4) {
+                gen_marker_key(rng)
+            } else {
+                (pool[rng.below(pool.len())], rng.chance(2, 3))
+            }
+        })
+        .collect()
+}
+
+// The frozen marker, or a signer threshold marker, valid or not, usually as
+// a non-signer key.
+fn gen_marker_key(rng: &mut Rng) -> (Pubkey, bool) {
+    if rng.chance(1, 4) {
+        (FROZEN_MARKER, rng.chance(3, 4))
+    } else {
+        (threshold_marker(rng.below(5) as u8), rng.chance(1, 4))
+    }
+}
+
+// The config account, and the keys stored in it.
+fn gen_config_account(
+    rng: &mut Rng,
+    mollusk: &Mollusk,
+    pool: &[Pubkey],
+    markers: bool,
+) -> (Account, Vec<(Pubkey, bool)>) {
+    let mut keys = vec![];
+    let mut data = match rng.below(8) {
+        // Uninitialized.
+        0 => vec![0; rng.below(200)],
+        // Garbage.
+        1 => rng.bytes(200),
+        _ => {
+            keys = gen_keys(rng, pool, markers);
+            bincode::serialize(&(ConfigKeys { keys: keys.clone() }, rng.bytes(48))).unwrap()
+        }
+    };
+    data.resize(data.len() + rng.below(160), 0);
+    let owner = if rng.chance(1, 16) {
+        rng.pubkey()
+    } else {
+        solana_config_program::id()
+    };
+    let account = Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    (account, keys)
+}
+
+// New keys, which often keep the stored keys, and `store` instruction data.
+fn gen_store_data(
+    rng: &mut Rng,
+    pool: &[Pubkey],
+    stored_keys: &[(Pubkey, bool)],
+    markers: bool,
+) -> (Vec<(Pubkey, bool)>, Vec<u8>) {
+    let keys = if rng.chance(1, 2) {
+        stored_keys.to_vec()
+    } else {
+        gen_keys(rng, pool, markers)
+    };
+    let mut data = bincode::serialize(&(ConfigKeys { keys: keys.clone() }, rng.bytes(64))).unwrap();
+    match rng.below(16) {
+        // Truncated.
+        0 => data.truncate(rng.below(data.len() + 1)),
+        // An invalid signer flag.
+        1 if !keys.is_empty() => data[1 + 33 * rng.below(keys.len()) + 32] = 2,
+        _ => {}
+    }
+    (keys, data)
+}
+
+fn gen_arbitrary_data(rng: &mut Rng) -> Vec<u8> {
+    let mut data = rng.bytes(160);
+    if data.first() == Some(&EXTENSION_PREFIX) {
+        data[0] = rng.below(usize::from(EXTENSION_PREFIX)) as u8;
+    }
+    data
+}
+
+// The config account, then the signers of `keys` in order, with some of them
+// dropped, swapped, replaced or not signing.
+fn gen_account_metas(rng: &mut Rng, pool: &[Pubkey], keys: &[(Pubkey, bool)]) -> Vec<AccountMeta> {
+    let config = pool[0];
+    let mut signers = keys
+        .iter()
+        .filter(|(_, is_signer)| *is_signer)
+        .map(|(pubkey, _)| *pubkey)
+        .collect::<Vec<_>>();
+    match rng.below(8) {
+        0 if !signers.is_empty() => {
+            signers.remove(rng.below(signers.len()));
+        }
+        1 if signers.len() > 1 => {
+            let i = rng.below(signers.len() - 1);
+            signers.swap(i, i + 1);
+        }
+        2 => signers.insert(rng.below(signers.len() + 1), pool[rng.below(pool.len())]),
+        _ => {}
+    }
+
+    let mut metas = vec![AccountMeta {
+        pubkey: config,
+        is_signer: rng.chance(3, 4),
+        is_writable: rng.chance(7, 8),
+    }];
+    metas.extend(signers.into_iter().map(|pubkey| AccountMeta {
+        pubkey,
+        is_signer: rng.chance(7, 8),
+        is_writable: false,
+    }));
+    metas
+}
+
+// Where the marker rules apply to a case: a frozen account, which the
+// current build rejects before any other check on the stored keys, a signer
+// threshold stored in the account, or a threshold marker among the new keys,
+// which the current build rejects unless it records a valid threshold. Both
+// programs first parse the new keys and check the account owner in the same
+// way.
+fn divergence(
+    config_account: &Account,
+    stored_keys: &[(Pubkey, bool)],
+    data: &[u8],
+    metas: &[AccountMeta],
+) -> Divergence {
+    let Ok(new_keys) = parse_store_keys(data) else {
+        return Divergence::None;
+    };
+    if config_account.owner != solana_config_program::id() {
+        return Divergence::None;
+    }
+    let stored_keys = ConfigKeys {
+        keys: stored_keys.to_vec(),
+    };
+    if stored_keys.is_frozen() {
+        Divergence::Frozen
+    } else if let Some(threshold) = stored_keys.threshold() {
+        Divergence::Threshold(threshold_model(
+            &stored_keys,
+            threshold,
+            &new_keys.to_config_keys(),
+            data,
+            config_account,
+            metas,
+        ))
+    } else if new_keys.threshold().is_some() {
+        Divergence::Errors(vec![
+            ConfigError::InvalidThreshold.or_legacy(ProgramError::InvalidArgument)
+        ])
+    } else {
+        Divergence::None
+    }
+}
+
+// The result of a `store` into an account storing signer threshold M: at
+// least M of the stored signers must sign, in any order, and the new keys
+// must keep the same signers and threshold. The new keys and the account
+// are then checked as for any other `store`.
+fn threshold_model(
+    stored_keys: &ConfigKeys,
+    threshold: u8,
+    new_keys: &ConfigKeys,
+    data: &[u8],
+    config_account: &Account,
+    metas: &[AccountMeta],
+) -> Result<(), ProgramError> {
+    let signers = |keys: &ConfigKeys| {
+        keys.keys
+            .iter()
+            .filter(|(_, is_signer)| *is_signer)
+            .map(|(pubkey, _)| *pubkey)
+            .collect::<Vec<_>>()
+    };
+    let (config_meta, signer_metas) = metas.split_first().unwrap();
+    // A stored signer equal to the config account signs as the config
+    // account.
+    let signed = |signer: &Pubkey| {
+        if *signer == config_meta.pubkey {
+            config_meta.is_signer
+        } else {
+            signer_metas
+                .iter()
+                .any(|meta| meta.pubkey == *signer && meta.is_signer)
+        }
+    };
+
+    let stored_signers = signers(stored_keys);
+    if stored_signers.is_empty() {
+        if !config_meta.is_signer {
+            return Err(
+                ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature)
+            );
+        }
+    } else if stored_signers
+        .iter()
+        .filter(|signer| signed(signer))
+        .count()
+        < usize::from(threshold)
+    {
+        return Err(ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature));
+    }
+
+    let new_signers = signers(new_keys);
+    if new_signers.iter().collect::<HashSet<_>>() != stored_signers.iter().collect()
+        || new_keys.threshold() != Some(threshold)
+    {
+        return Err(ConfigError::SignersChanged.or_legacy(ProgramError::InvalidArgument));
+    }
+
+    let new_thresholds = new_keys
+        .keys
+        .iter()
+        .filter(|(pubkey, is_signer)| !is_signer && parse_threshold_marker(pubkey).is_some())
+        .count();
+    if new_thresholds > 1 || threshold == 0 || usize::from(threshold) > new_signers.len() {
+        return Err(ConfigError::InvalidThreshold.or_legacy(ProgramError::InvalidArgument));
+    }
+    if new_keys.keys.iter().collect::<HashSet<_>>().len() != new_keys.keys.len() {
+        return Err(ConfigError::DuplicateKeys.or_legacy(ProgramError::InvalidArgument));
+    }
+    if config_account.data.len() < data.len() {
+        return Err(
+            ConfigError::InstructionDataTooLarge.or_legacy(ProgramError::InvalidInstructionData)
+        );
+    }
+    if !config_meta.is_writable {
+        return Err(ConfigError::ReadonlyDataModified.into());
+    }
+    Ok(())
+}
+
+fn gen_case(rng: &mut Rng, mollusk: &Mollusk, mode: Mode) -> Case {
+    let markers = matches!(mode, Mode::Markers);
+    let pool = key_pool(rng);
+    let (config_account, stored_keys) = gen_config_account(rng, mollusk, &pool, markers);
+
+    let (keys, data) = match mode {
+        Mode::ArbitraryData => (gen_keys(rng, &pool, false), gen_arbitrary_data(rng)),
+        Mode::Store | Mode::Markers => gen_store_data(rng, &pool, &stored_keys, markers),
+    };
+    let metas = gen_account_metas(rng, &pool, &keys);
+    let divergence = divergence(&config_account, &stored_keys, &data, &metas);
+
+    let mut accounts = vec![(pool[0], config_account)];
+    for meta in &metas {
+        if !accounts.iter().any(|(pubkey, _)| *pubkey == meta.pubkey) {
+            accounts.push((meta.pubkey, Account::default()));
+        }
+    }
+
+    Case {
+        instruction: Instruction::new_with_bytes(solana_config_program::id(), &data, metas),
+        accounts,
+        divergence,
+    }
+}
+
+fn assert_equivalent(
+    case_index: usize,
+    seed: u64,
+    case: &Case,
+    current: &InstructionResult,
+    regression: &InstructionResult,
+) {
+    let context = || {
+        format!(
+            "case {case_index} (seed {seed:#x})\ninstruction: {:?}\naccounts: {:?}",
+            case.instruction, case.accounts
+        )
+    };
+
+    // With detailed errors, the current build returns its own error codes,
+    // so only success is compared.
+    let results_agree = if cfg!(feature = "detailed-errors") {
+        current.raw_result.is_ok() == regression.raw_result.is_ok()
+    } else {
+        current.raw_result == regression.raw_result
+    };
+    match &case.divergence {
+        Divergence::None => assert!(
+            results_agree,
+            "results differ: {:?} and {:?}\n{}",
+            current.raw_result,
+            regression.raw_result,
+            context(),
+        ),
+        Divergence::Frozen => {
+            assert_eq!(
+                current.program_result,
+                ProgramResult::Failure(
+                    ConfigError::AccountFrozen.or_legacy(ProgramError::MissingRequiredSignature)
+                ),
+                "frozen account not rejected\n{}",
+                context(),
+            );
+            return;
+        }
+        Divergence::Errors(errors) if !results_agree => {
+            assert!(
+                matches!(&current.program_result, ProgramResult::Failure(error) if errors.contains(error)),
+                "results differ: {:?} and {:?}, expected one of {errors:?}\n{}",
+                current.raw_result,
+                regression.raw_result,
+                context(),
+            );
+            return;
+        }
+        Divergence::Errors(_) => {}
+        // The marker rules run different checks, so the results and compute
+        // units aren't comparable. A successful store must write the
+        // instruction data and leave the rest of the account untouched.
+        Divergence::Threshold(expected) => {
+            let expected = match expected {
+                Ok(()) => ProgramResult::Success,
+                Err(error) => ProgramResult::Failure(error.clone()),
+            };
+            assert_eq!(
+                current.program_result,
+                expected,
+                "threshold store result differs from the model\n{}",
+                context(),
+            );
+            if current.program_result.is_ok() {
+                let data = &case.instruction.data;
+                let (_, original) = &case.accounts[0];
+                let (_, config_account) = &current.resulting_accounts[0];
+                assert_eq!(
+                    &config_account.data[..data.len()],
+                    &data[..],
+                    "instruction data not stored\n{}",
+                    context(),
+                );
+                assert_eq!(
+                    &config_account.data[data.len()..],
+                    &original.data[data.len()..],
+                    "data past the stored instruction data changed\n{}",
+                    context(),
+                );
+            }
+            return;
+        }
+    }
+
+    assert_eq!(
+        current.resulting_accounts,
+        regression.resulting_accounts,
+        "resulting accounts differ\n{}",
+        context(),
+    );
+
+    let envelope = regression.compute_units_consumed * COMPUTE_UNIT_ENVELOPE_PERCENT / 100;
+    assert!(
+        current.compute_units_consumed <= envelope,
+        "used {} compute units, above the envelope of {envelope}\n{}",
+        current.compute_units_consumed,
+        context(),
+    );
+}
+
+fn run_differential(mode: Mode) {
+    let current = setup();
+    let regression = setup_regression();
+
+    let seed = env_or("DIFFERENTIAL_SEED", DEFAULT_SEED);
+    let cases = env_or("DIFFERENTIAL_CASES", DEFAULT_CASES);
+    let mut rng = Rng(seed ^ mode as u64);
+
+    for case_index in 0..cases {
+        let case = gen_case(&mut rng, &current, mode);
+        let current_result = current.process_instruction(&case.instruction, &case.accounts);
+        let regression_result = regression.process_instruction(&case.instruction, &case.accounts);
+        assert_equivalent(case_index, seed, &case, &current_result, &regression_result);
+    }
+}
+
+#[test]
+fn test_differential_store() {
+    run_differential(Mode::Store);
+}
+
+#[test]
+fn test_differential_arbitrary_data() {
+    run_differential(Mode::ArbitraryData);
+}
+
+#[test]
+fn test_differential_markers() {
+    run_differential(Mode::Markers);
+}
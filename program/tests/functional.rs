@@ -2,12 +2,13 @@
 
 use {
     bincode::serialized_size,
-    mollusk_svm::{result::Check, Mollusk},
+    mollusk_svm::{program::keyed_account_for_system_program, result::Check, Mollusk},
     serde::{Deserialize, Serialize},
     solana_account::Account,
     solana_config_interface::{
-        instruction::{self as config_instruction},
-        state::ConfigKeys,
+        instruction::{self as config_instruction, MAX_STORE_KEYS},
+        space::ConfigSpace,
+        state::{threshold_marker, ConfigKeys, ConfigKeysRef, FROZEN_MARKER},
     },
     solana_config_program::error::ConfigError,
     solana_instruction::{AccountMeta, Instruction},
@@ -130,7 +131,9 @@ fn test_process_store_fail_instruction_data_too_large() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, config_account)],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InstructionDataTooLarge.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 }
 
@@ -150,7 +153,9 @@ fn test_process_store_fail_account0_not_signer() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, config_account)],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 }
 
@@ -210,7 +215,9 @@ fn test_process_store_bad_config_account() {
                 Account::new(100_000, 0, &solana_config_program::id()),
             ),
         ],
-        &[Check::err(ProgramError::InvalidAccountData)],
+        &[Check::err(
+            ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData),
+        )],
     );
 }
 
@@ -238,7 +245,9 @@ fn test_process_store_with_bad_additional_signer() {
             (config, config_account.clone()),
             (bad_signer, Account::default()),
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::SignerMismatch.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 
     // Config-data pubkey not a signer.
@@ -248,7 +257,9 @@ fn test_process_store_with_bad_additional_signer() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, config_account), (signer0, Account::default())],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 }
 
@@ -278,7 +289,9 @@ fn test_store_requiring_config() {
             (config, config_account.clone()),
             (signer, Account::default()),
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 
     // This is kind of strange, since the `store` helper was taken directly
@@ -362,7 +375,9 @@ fn test_config_updates() {
             // Missing signer0.
             (signer1, Account::default()),
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 
     // Do it again, this time missing signer1.
@@ -378,7 +393,9 @@ fn test_config_updates() {
             (signer0, Account::default()),
             // Missing signer1.
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 
     // Attempt update with incorrect signatures.
@@ -395,7 +412,9 @@ fn test_config_updates() {
             (signer0, Account::default()),
             (signer2, Account::default()), // Incorrect signer1.
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::UnknownSigner.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 }
 
@@ -426,7 +445,9 @@ fn test_config_initialize_contains_duplicates_fails() {
             (signer0, Account::default()),
             (signer0, Account::default()), // Duplicate signer0.
         ],
-        &[Check::err(ProgramError::InvalidArgument)],
+        &[Check::err(
+            ConfigError::DuplicateKeys.or_legacy(ProgramError::InvalidArgument),
+        )],
     );
 }
 
@@ -470,7 +491,9 @@ fn test_config_update_contains_duplicates_fails() {
             (signer0, Account::default()),
             (signer0, Account::default()), // Duplicate signer0.
         ],
-        &[Check::err(ProgramError::InvalidArgument)],
+        &[Check::err(
+            ConfigError::DuplicateKeys.or_legacy(ProgramError::InvalidArgument),
+        )],
     );
 }
 
@@ -534,7 +557,9 @@ fn test_config_updates_requiring_config() {
             (config, updated_config_account),
             (signer0, Account::default()),
         ],
-        &[Check::err(ProgramError::MissingRequiredSignature)],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
     );
 }
 
@@ -543,14 +568,13 @@ fn test_config_initialize_no_panic() {
     let mollusk = setup();
 
     let config = Pubkey::new_unique();
-    let max_space = serialized_size(&MyConfig::default()).unwrap();
+    let space = ConfigSpace::for_payload(&[], &MyConfig::default()).unwrap();
 
-    let instructions = config_instruction::create_account_with_max_config_space::<MyConfig>(
+    let instructions = config_instruction::create_account_with_space::<MyConfig>(
         &Pubkey::new_unique(),
         &config,
         1,
-        max_space,
-        vec![],
+        &space,
     );
     let mut instruction = instructions[1].clone();
     instruction.accounts = vec![];
@@ -585,7 +609,9 @@ fn test_config_bad_owner() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, config_account), (signer0, Account::default())],
-        &[Check::err(ProgramError::InvalidAccountOwner)],
+        &[Check::err(
+            ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner),
+        )],
     );
 }
 
@@ -637,7 +663,9 @@ fn test_maximum_keys_input() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, updated_config_account)],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 }
 
@@ -655,21 +683,27 @@ fn test_safe_deserialize() {
         // Empty buffer. Not a valid `ShortU16`.
         &build_instruction(&[]),
         &[],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 
     mollusk.process_and_validate_instruction(
         // `ShortU16` value of 38. One byte too large.
         &build_instruction(&[0x26]),
         &[],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 
     mollusk.process_and_validate_instruction(
         // `ShortU16` value of 37. OK for vector size, but no keys following.
         &build_instruction(&[0x25]),
         &[],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 
     // Now try with some actual `ConfigKeys` inputs.
@@ -699,7 +733,9 @@ fn test_safe_deserialize() {
         // `ShortU16` value of 38. Err.
         &build_instruction(&serialized_config_keys(&keys)),
         &[],
-        &[Check::err(ProgramError::InvalidInstructionData)],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
     );
 }
 
@@ -732,7 +768,9 @@ fn test_safe_deserialize_from_state() {
     mollusk.process_and_validate_instruction(
         &instruction,
         &[(config, config_account)],
-        &[Check::err(ProgramError::InvalidAccountData)],
+        &[Check::err(
+            ConfigError::InvalidAccountState.or_legacy(ProgramError::InvalidAccountData),
+        )],
     );
 }
 
@@ -763,3 +801,1186 @@ fn test_write_same_data_to_readonly() {
         ))],
     );
 }
+
+#[test]
+fn test_close() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+
+    let pubkey = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let keys = vec![(pubkey, false), (signer0, true), (signer1, true)];
+
+    let config_account = create_config_account(&mollusk, keys);
+    let config_lamports = config_account.lamports;
+    let destination_account = Account::new(1_000, 0, &Pubkey::default());
+
+    let instruction = config_instruction::close(&config, &destination, &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (destination, destination_account.clone()),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config).closed().build(),
+            Check::account(&destination)
+                .lamports(1_000 + config_lamports)
+                .build(),
+        ],
+    );
+
+    // Stored signer didn't sign.
+    let mut instruction = config_instruction::close(&config, &destination, &[signer0, signer1]);
+    instruction.accounts[3].is_signer = false;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (destination, destination_account.clone()),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    // Stored signer missing from the accounts.
+    let mut instruction = config_instruction::close(&config, &destination, &[signer0, signer1]);
+    instruction.accounts.pop();
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (destination, destination_account),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
+
+#[test]
+fn test_close_requiring_config() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+
+    // No stored signers, so the config account must sign.
+    let config_account = create_config_account(&mollusk, vec![]);
+
+    let instruction = config_instruction::close(&config, &destination, &[]);
+    assert!(instruction.accounts[0].is_signer);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (destination, Account::default()),
+        ],
+        &[Check::success(), Check::account(&config).closed().build()],
+    );
+
+    let mut instruction = config_instruction::close(&config, &destination, &[]);
+    instruction.accounts[0].is_signer = false;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (destination, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    // Can't close into itself.
+    let instruction = config_instruction::close(&config, &config, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account)],
+        &[Check::err(
+            ConfigError::InvalidDestination.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+}
+
+#[test]
+fn test_close_bad_owner() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+
+    let config_account = Account::new(100_000, get_config_space(0), &Pubkey::new_unique());
+
+    let instruction = config_instruction::close(&config, &destination, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account), (destination, Account::default())],
+        &[Check::err(
+            ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner),
+        )],
+    );
+}
+
+#[test]
+fn test_resize_grow() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+
+    let signer0 = Pubkey::new_unique();
+    let keys = vec![(signer0, true)];
+    let my_config = MyConfig::new(42);
+
+    let config_account = {
+        let mut account = create_config_account(&mollusk, keys.clone());
+        account.data = bincode::serialize(&(ConfigKeys { keys }, my_config)).unwrap();
+        account
+    };
+    let config_data = config_account.data.clone();
+    let config_lamports = config_account.lamports;
+    let payer_lamports = 1_000_000_000;
+
+    let new_len = config_data.len() + 100;
+    let new_lamports = mollusk.sysvars.rent.minimum_balance(new_len);
+    let mut new_data = config_data.clone();
+    new_data.resize(new_len, 0);
+
+    let (system_program, system_program_account) = keyed_account_for_system_program();
+
+    let instruction = config_instruction::resize(&config, &payer, new_len as u64, &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (payer, Account::new(payer_lamports, 0, &system_program)),
+            (system_program, system_program_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&new_data)
+                .lamports(new_lamports)
+                .build(),
+            Check::account(&payer)
+                .lamports(payer_lamports - (new_lamports - config_lamports))
+                .build(),
+        ],
+    );
+
+    // Stored signer didn't sign.
+    let mut instruction = config_instruction::resize(&config, &payer, new_len as u64, &[signer0]);
+    instruction.accounts[3].is_signer = false;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (payer, Account::new(payer_lamports, 0, &system_program)),
+            (system_program, system_program_account),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
+
+#[test]
+fn test_resize_shrink() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let destination = Pubkey::new_unique();
+
+    let keys = vec![(Pubkey::new_unique(), false)];
+    let my_config = MyConfig::new(42);
+
+    // Over-allocated config account.
+    let config_data = bincode::serialize(&(ConfigKeys { keys: keys.clone() }, my_config)).unwrap();
+    let config_account = {
+        let space = config_data.len() + 1_000;
+        let mut account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(space),
+            space,
+            &solana_config_program::id(),
+        );
+        account.data[..config_data.len()].copy_from_slice(&config_data);
+        account
+    };
+    let config_lamports = config_account.lamports;
+    let new_lamports = mollusk.sysvars.rent.minimum_balance(config_data.len());
+
+    let (system_program, system_program_account) = keyed_account_for_system_program();
+
+    // No stored signers, so the config account signs.
+    let instruction =
+        config_instruction::resize(&config, &destination, config_data.len() as u64, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (destination, Account::default()),
+            (system_program, system_program_account.clone()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&config_data)
+                .lamports(new_lamports)
+                .build(),
+            Check::account(&destination)
+                .lamports(config_lamports - new_lamports)
+                .build(),
+        ],
+    );
+
+    // Can't shrink into the stored keys.
+    let keys_len = serialized_size(&ConfigKeys { keys }).unwrap();
+    let instruction = config_instruction::resize(&config, &destination, keys_len - 1, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (destination, Account::default()),
+            (system_program, system_program_account),
+        ],
+        &[Check::err(
+            ConfigError::KeysTruncated.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+}
+
+#[test]
+fn test_resize_over_funded() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let funding = Pubkey::new_unique();
+
+    let keys = vec![(Pubkey::new_unique(), false)];
+    let config_data = bincode::serialize(&(ConfigKeys { keys }, MyConfig::new(42))).unwrap();
+    let space = config_data.len() + 100;
+    let rent = &mollusk.sysvars.rent;
+    // A third party deposited lamports beyond the rent-exempt minimum.
+    let extra_lamports = 1_000_000_000;
+    let config_lamports = rent.minimum_balance(space) + extra_lamports;
+    let config_account = {
+        let mut account = Account::new(config_lamports, space, &solana_config_program::id());
+        account.data[..config_data.len()].copy_from_slice(&config_data);
+        account
+    };
+
+    let (system_program, system_program_account) = keyed_account_for_system_program();
+    let accounts = [
+        (config, config_account),
+        (funding, Account::default()),
+        (system_program, system_program_account),
+    ];
+
+    // Growing within the balance, or keeping the length, refunds nothing.
+    for new_len in [space, space + 100] {
+        let instruction = config_instruction::resize(&config, &funding, new_len as u64, &[]);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[
+                Check::success(),
+                Check::account(&config)
+                    .space(new_len)
+                    .lamports(config_lamports)
+                    .build(),
+                Check::account(&funding).lamports(0).build(),
+            ],
+        );
+    }
+
+    // Shrinking refunds only the rent freed by the shrink.
+    let new_len = config_data.len();
+    let refund = rent.minimum_balance(space) - rent.minimum_balance(new_len);
+    let instruction = config_instruction::resize(&config, &funding, new_len as u64, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&config_data)
+                .lamports(config_lamports - refund)
+                .build(),
+            Check::account(&funding).lamports(refund).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_write() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+
+    let pubkey = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let keys = vec![(pubkey, false), (signer0, true)];
+
+    let config_account = create_config_account(&mollusk, keys.clone());
+    let data_len = config_account.data.len();
+
+    // Overwrite the payload in place.
+    let new_config = bincode::serialize(&MyConfig::new(42)).unwrap();
+    let instruction = config_instruction::write(&config, 0, new_config.clone(), &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(
+                    &bincode::serialize(&(ConfigKeys { keys: keys.clone() }, MyConfig::new(42)))
+                        .unwrap(),
+                )
+                .build(),
+        ],
+    );
+
+    // Patch a single byte at an offset.
+    let keys_len = serialized_size(&ConfigKeys { keys }).unwrap() as usize;
+    let mut patched_data = config_account.data.clone();
+    patched_data[keys_len + 1] = 0xAB;
+    let instruction = config_instruction::write(&config, 1, vec![0xAB], &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config).data(&patched_data).build(),
+        ],
+    );
+
+    // Write past the end of the account.
+    let offset = (data_len - keys_len) as u32;
+    let instruction = config_instruction::write(&config, offset - 1, vec![1, 2], &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::WriteOutOfBounds.or_legacy(ProgramError::AccountDataTooSmall),
+        )],
+    );
+
+    // A data length longer than the instruction is rejected before reading
+    // the data.
+    let mut instruction = config_instruction::write(&config, 0, vec![], &[signer0]);
+    instruction.data.truncate(9);
+    instruction.data.extend(u64::MAX.to_le_bytes());
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::InvalidInstruction.or_legacy(ProgramError::InvalidInstructionData),
+        )],
+    );
+
+    // Stored signer didn't sign.
+    let mut instruction = config_instruction::write(&config, 0, new_config, &[signer0]);
+    instruction.accounts[1].is_signer = false;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account), (signer0, Account::default())],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
+
+#[test]
+fn test_threshold_store() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let signer2 = Pubkey::new_unique();
+    let keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (signer2, true),
+        (threshold_marker(2), false),
+    ];
+    let my_config = MyConfig::new(42);
+
+    // Initialize with a 2-of-3 threshold, which requires every signer.
+    let config_account = {
+        let space = get_config_space(keys.len());
+        let lamports = mollusk.sysvars.rent.minimum_balance(space);
+        Account::new(lamports, space, &solana_config_program::id())
+    };
+    let instruction = config_instruction::store(&config, true, keys.clone(), &my_config);
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+            (signer2, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&bincode::serialize(&(ConfigKeys { keys: keys.clone() }, my_config)).unwrap())
+                .build(),
+        ],
+    );
+    let config_account = result.get_account(&config).unwrap().clone();
+
+    // Update signed by any two of the three signers, in any order.
+    let new_config = MyConfig::new(84);
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        keys.clone(),
+        &new_config,
+        &[signer2, signer0],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer2, Account::default()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(
+                    &bincode::serialize(&(ConfigKeys { keys: keys.clone() }, &new_config)).unwrap(),
+                )
+                .build(),
+        ],
+    );
+
+    // Update signed by only one signer.
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        keys.clone(),
+        &new_config,
+        &[signer1],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    // Update signed by one signer twice.
+    let mut instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        keys.clone(),
+        &new_config,
+        &[signer1],
+    );
+    instruction.accounts.push(AccountMeta::new(signer1, true));
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    // Update attempting to replace a signer.
+    let signer3 = Pubkey::new_unique();
+    let new_keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (signer3, true),
+        (threshold_marker(2), false),
+    ];
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        new_keys,
+        &new_config,
+        &[signer0, signer1],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::SignersChanged.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+
+    // Update attempting to lower the threshold.
+    let new_keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (signer2, true),
+        (threshold_marker(1), false),
+    ];
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        new_keys,
+        &new_config,
+        &[signer0, signer1],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::SignersChanged.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+}
+
+#[test]
+fn test_threshold_invalid() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let my_config = MyConfig::new(42);
+
+    let config_account = {
+        let space = get_config_space(4);
+        let lamports = mollusk.sysvars.rent.minimum_balance(space);
+        Account::new(lamports, space, &solana_config_program::id())
+    };
+
+    for keys in [
+        // Zero threshold.
+        vec![
+            (signer0, true),
+            (signer1, true),
+            (threshold_marker(0), false),
+        ],
+        // Threshold above the number of signers.
+        vec![
+            (signer0, true),
+            (signer1, true),
+            (threshold_marker(3), false),
+        ],
+        // More than one threshold.
+        vec![
+            (signer0, true),
+            (signer1, true),
+            (threshold_marker(1), false),
+            (threshold_marker(2), false),
+        ],
+    ] {
+        let instruction = config_instruction::store(&config, true, keys, &my_config);
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &[
+                (config, config_account.clone()),
+                (signer0, Account::default()),
+                (signer1, Account::default()),
+            ],
+            &[Check::err(
+                ConfigError::InvalidThreshold.or_legacy(ProgramError::InvalidArgument),
+            )],
+        );
+    }
+}
+
+#[test]
+fn test_threshold_write() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (threshold_marker(1), false),
+    ];
+
+    let config_account = create_config_account(&mollusk, keys.clone());
+
+    let new_config = bincode::serialize(&MyConfig::new(42)).unwrap();
+    let instruction = config_instruction::write(&config, 0, new_config.clone(), &[signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer1, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&bincode::serialize(&(ConfigKeys { keys }, MyConfig::new(42))).unwrap())
+                .build(),
+        ],
+    );
+
+    // No stored signer signed.
+    let instruction = config_instruction::write(&config, 0, new_config, &[]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account)],
+        &[Check::err(
+            ConfigError::TooFewSigners.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
+
+#[test]
+fn test_compare_and_store() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let keys = vec![(signer0, true)];
+
+    let config_account = create_config_account(&mollusk, keys.clone());
+    let current_hash = ConfigKeysRef::new(&config_account.data)
+        .unwrap()
+        .data_hash();
+
+    let my_config = MyConfig::new(42);
+    let store = config_instruction::store(&config, false, keys.clone(), &my_config);
+
+    // Current data matches the expected hash.
+    let instruction = config_instruction::compare_and_store(store.clone(), &current_hash);
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&bincode::serialize(&(ConfigKeys { keys }, &my_config)).unwrap())
+                .build(),
+        ],
+    );
+
+    // Current data has since changed.
+    let updated_account = result.get_account(&config).unwrap().clone();
+    let instruction = config_instruction::compare_and_store(store.clone(), &current_hash);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, updated_account), (signer0, Account::default())],
+        &[Check::err(ProgramError::Custom(
+            ConfigError::DataHashMismatch as u32,
+        ))],
+    );
+
+    // A `store` data length longer than the instruction is rejected before
+    // reading the data.
+    let mut instruction = config_instruction::compare_and_store(store.clone(), &current_hash);
+    instruction.data.truncate(37);
+    instruction.data.extend(u64::MAX.to_le_bytes());
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::InvalidInstruction.or_legacy(ProgramError::InvalidInstructionData),
+        )],
+    );
+
+    // Stored signer didn't sign.
+    let mut instruction = config_instruction::compare_and_store(store, &current_hash);
+    instruction.accounts[1].is_signer = false;
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account), (signer0, Account::default())],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
+
+#[test]
+fn test_set_keys() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let pubkey = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let keys = vec![(pubkey, false), (signer0, true)];
+    let my_config = MyConfig::new(42);
+
+    // Leave room for one more key after the payload.
+    let space = get_config_space(keys.len() + 1);
+    let config_account = {
+        let mut data =
+            bincode::serialize(&(ConfigKeys { keys: keys.clone() }, &my_config)).unwrap();
+        data.resize(space, 0);
+        let mut account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(space),
+            space,
+            &solana_config_program::id(),
+        );
+        account.data = data;
+        account
+    };
+    let expected_data = |keys: Vec<(Pubkey, bool)>| {
+        let mut data = bincode::serialize(&(ConfigKeys { keys }, &my_config)).unwrap();
+        data.resize(space, 0);
+        data
+    };
+
+    // Add a brand-new signer.
+    let new_keys = vec![(pubkey, false), (signer0, true), (signer1, true)];
+    let instruction = config_instruction::set_keys(&config, new_keys.clone(), &[signer0]);
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&expected_data(new_keys.clone()))
+                .build(),
+        ],
+    );
+    let grown_account = result.get_account(&config).unwrap().clone();
+
+    // Remove the original signer, which now requires both signers.
+    let new_keys = vec![(pubkey, false), (signer1, true)];
+    let instruction = config_instruction::set_keys(&config, new_keys.clone(), &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, grown_account.clone()),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&expected_data(new_keys))
+                .build(),
+        ],
+    );
+
+    // Stored signer didn't sign.
+    let new_keys = vec![(pubkey, false), (signer1, true)];
+    let instruction = config_instruction::set_keys(&config, new_keys, &[signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, grown_account.clone()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    // Duplicate keys.
+    let new_keys = vec![(signer0, true), (signer0, true)];
+    let instruction = config_instruction::set_keys(&config, new_keys, &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::DuplicateKeys.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+
+    // Another key would push the payload off the end of the account.
+    let new_keys = vec![
+        (pubkey, false),
+        (signer0, true),
+        (signer1, true),
+        (Pubkey::new_unique(), false),
+    ];
+    let instruction = config_instruction::set_keys(&config, new_keys, &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, grown_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::DataTruncated.or_legacy(ProgramError::AccountDataTooSmall),
+        )],
+    );
+
+    // A key count too large to allocate is rejected before reading the keys.
+    let mut instruction = config_instruction::set_keys(&config, vec![], &[signer0]);
+    instruction.data.truncate(5);
+    instruction.data.extend([0xFF, 0xFF, 0x03]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
+    );
+
+    // So is one that fits in the input but is above the `store` limit.
+    let too_many_keys = (0..=MAX_STORE_KEYS)
+        .map(|_| (Pubkey::new_unique(), false))
+        .collect();
+    let instruction = config_instruction::set_keys(&config, too_many_keys, &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account), (signer0, Account::default())],
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
+    );
+}
+
+#[test]
+fn test_set_keys_threshold() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let signer2 = Pubkey::new_unique();
+    let keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (threshold_marker(2), false),
+    ];
+    let config_account = create_config_account(&mollusk, keys);
+
+    // Any two signers can replace the third.
+    let new_keys = vec![
+        (signer0, true),
+        (signer2, true),
+        (threshold_marker(2), false),
+    ];
+    let instruction = config_instruction::set_keys(&config, new_keys.clone(), &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(
+                    &bincode::serialize(&(ConfigKeys { keys: new_keys }, MyConfig::default()))
+                        .unwrap(),
+                )
+                .build(),
+        ],
+    );
+
+    // The new threshold must still be valid.
+    let new_keys = vec![(signer0, true), (threshold_marker(2), false)];
+    let instruction = config_instruction::set_keys(&config, new_keys, &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::InvalidThreshold.or_legacy(ProgramError::InvalidArgument),
+        )],
+    );
+}
+
+#[test]
+fn test_freeze() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let keys = vec![(signer0, true), (signer1, true)];
+    let my_config = MyConfig::new(42);
+
+    // Leave room for the frozen marker after the payload.
+    let space = get_config_space(keys.len() + 1);
+    let config_account = {
+        let mut data =
+            bincode::serialize(&(ConfigKeys { keys: keys.clone() }, &my_config)).unwrap();
+        data.resize(space, 0);
+        let mut account = Account::new(
+            mollusk.sysvars.rent.minimum_balance(space),
+            space,
+            &solana_config_program::id(),
+        );
+        account.data = data;
+        account
+    };
+
+    // All stored signers must sign.
+    let instruction = config_instruction::freeze(&config, &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::MissingSignature.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    let mut frozen_keys = keys.clone();
+    frozen_keys.push((FROZEN_MARKER, true));
+    let instruction = config_instruction::freeze(&config, &[signer0, signer1]);
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[
+            Check::success(),
+            Check::account(&config)
+                .data(&bincode::serialize(&(ConfigKeys { keys: frozen_keys }, &my_config)).unwrap())
+                .build(),
+        ],
+    );
+    let frozen_account = result.get_account(&config).unwrap().clone();
+    let accounts = [
+        (config, frozen_account),
+        (signer0, Account::default()),
+        (signer1, Account::default()),
+    ];
+
+    // No instruction can modify the frozen account.
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        keys.clone(),
+        &MyConfig::new(43),
+        &[signer0, signer1],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+
+    let instruction = config_instruction::write(&config, 0, vec![1], &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::Immutable),
+        )],
+    );
+
+    let instruction = config_instruction::set_keys(&config, keys, &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::Immutable),
+        )],
+    );
+
+    let instruction = config_instruction::freeze(&config, &[signer0, signer1]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &accounts,
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::Immutable),
+        )],
+    );
+
+    let destination = Pubkey::new_unique();
+    let instruction = config_instruction::close(&config, &destination, &[signer0, signer1]);
+    let mut close_accounts = accounts.to_vec();
+    close_accounts.insert(1, (destination, Account::default()));
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &close_accounts,
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::Immutable),
+        )],
+    );
+}
+
+#[test]
+fn test_store_frozen_checked_first() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    // The original builtin treats the frozen marker as an ordinary signer,
+    // so it would accept this `store` when given a signing marker account.
+    let keys = vec![(signer0, true), (FROZEN_MARKER, true)];
+    let config_account = create_config_account(&mollusk, keys.clone());
+    let accounts = [
+        (config, config_account.clone()),
+        (signer0, Account::default()),
+        (FROZEN_MARKER, Account::default()),
+    ];
+
+    // The frozen check comes before the checks on signers, keys, data length
+    // and writability.
+    let instruction = config_instruction::store(&config, false, keys.clone(), &MyConfig::new(43));
+    let mut unsigned = instruction.clone();
+    unsigned.accounts[1].is_signer = false;
+    let mut duplicate_keys = keys.clone();
+    duplicate_keys.push((signer0, false));
+    let mut readonly = instruction.clone();
+    readonly.accounts[0].is_writable = false;
+    for instruction in [
+        instruction.clone(),
+        unsigned,
+        config_instruction::store(&config, false, duplicate_keys, &MyConfig::new(43)),
+        config_instruction::store(&config, false, keys.clone(), &vec![0u8; 64]),
+        readonly,
+    ] {
+        mollusk.process_and_validate_instruction(
+            &instruction,
+            &accounts,
+            &[Check::err(
+                ConfigError::AccountFrozen.or_legacy(ProgramError::MissingRequiredSignature),
+            )],
+        );
+    }
+
+    // Invalid new keys and the account owner are still checked first.
+    let mut invalid_keys = instruction.clone();
+    invalid_keys.data[0] = u8::MAX;
+    mollusk.process_and_validate_instruction(
+        &invalid_keys,
+        &accounts,
+        &[Check::err(
+            ConfigError::InvalidConfigKeys.or_legacy(ProgramError::InvalidInstructionData),
+        )],
+    );
+    let mut not_owned = config_account;
+    not_owned.owner = Pubkey::new_unique();
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, not_owned),
+            (signer0, Account::default()),
+            (FROZEN_MARKER, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::InvalidAccountOwner.or_legacy(ProgramError::InvalidAccountOwner),
+        )],
+    );
+}
+
+#[test]
+fn test_freeze_threshold() {
+    let mollusk = setup();
+
+    let config = Pubkey::new_unique();
+    let signer0 = Pubkey::new_unique();
+    let signer1 = Pubkey::new_unique();
+    let keys = vec![
+        (signer0, true),
+        (signer1, true),
+        (threshold_marker(1), false),
+    ];
+    let config_account = create_config_account(&mollusk, keys.clone());
+
+    // The frozen marker doesn't fit in a full account.
+    let instruction = config_instruction::freeze(&config, &[signer0]);
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, config_account.clone()),
+            (signer0, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::DataTruncated.or_legacy(ProgramError::AccountDataTooSmall),
+        )],
+    );
+
+    // Any one signer can freeze an account with a threshold of one.
+    let mut config_account = config_account;
+    let space = get_config_space(keys.len() + 1);
+    config_account.data.resize(space, 0);
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &[(config, config_account), (signer0, Account::default())],
+        &[Check::success()],
+    );
+    let frozen_account = result.get_account(&config).unwrap().clone();
+    assert!(ConfigKeysRef::new(&frozen_account.data)
+        .unwrap()
+        .is_frozen());
+
+    // Not even the full signer set can update it.
+    let instruction = config_instruction::store_with_signers(
+        &config,
+        false,
+        keys,
+        &MyConfig::new(43),
+        &[signer0, signer1],
+    );
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &[
+            (config, frozen_account),
+            (signer0, Account::default()),
+            (signer1, Account::default()),
+        ],
+        &[Check::err(
+            ConfigError::AccountFrozen.or_legacy(ProgramError::MissingRequiredSignature),
+        )],
+    );
+}
@@ -0,0 +1,289 @@
+//! Property tests for the `store` acceptance rules.
+//!
+//! Random stored keys, new keys, signer accounts, account sizes and
+//! writability are run through the program, and the result is compared with
+//! `model_store`, an independent model of the rules:
+//!
+//! - The config account must be owned by the program.
+//! - The config account must sign if no signers are stored.
+//! - Each new signer other than the config account must sign, in the account
+//!   following the config account at the same position among the signers, and
+//!   must be a stored signer if any keys are stored.
+//! - The new keys must not contain duplicates.
+//! - Every stored signer must sign.
+//! - The instruction data must fit in the config account.
+//! - The config account must be writable.
+
+#![allow(clippy::arithmetic_side_effects)]
+
+use {
+    mollusk_svm::{result::ProgramResult, Mollusk},
+    proptest::prelude::*,
+    solana_account::Account,
+    solana_config_interface::state::ConfigKeys,
+    solana_config_program::error::ConfigError,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_program_error::ProgramError,
+    solana_pubkey::Pubkey,
+};
+
+const POOL_SIZE: usize = 5;
+
+fn setup() -> Mollusk {
+    Mollusk::new(&solana_config_program::id(), "solana_config_program")
+}
+
+// Keys are drawn from a small pool, starting with the config account, so
+// that stored keys, new keys and signer accounts overlap often.
+fn pool_key(index: usize) -> Pubkey {
+    Pubkey::new_from_array([index as u8 + 1; 32])
+}
+
+fn config_key() -> Pubkey {
+    pool_key(0)
+}
+
+#[derive(Clone, Debug)]
+struct StoreCase {
+    stored_keys: Vec<(Pubkey, bool)>,
+    new_keys: Vec<(Pubkey, bool)>,
+    payload_len: usize,
+    // The config account's space beyond the stored keys.
+    spare_space: usize,
+    is_owned: bool,
+    config_is_signer: bool,
+    config_is_writable: bool,
+    signer_accounts: Vec<(Pubkey, bool)>,
+}
+
+impl StoreCase {
+    fn account_space(&self) -> usize {
+        bincode::serialized_size(&ConfigKeys {
+            keys: self.stored_keys.clone(),
+        })
+        .unwrap() as usize
+            + self.spare_space
+    }
+
+    fn instruction_data(&self) -> Vec<u8> {
+        bincode::serialize(&(
+            ConfigKeys {
+                keys: self.new_keys.clone(),
+            },
+            vec![7u8; self.payload_len],
+        ))
+        .unwrap()
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![AccountMeta {
+            pubkey: config_key(),
+            is_signer: self.config_is_signer,
+            is_writable: self.config_is_writable,
+        }];
+        metas.extend(
+            self.signer_accounts
+                .iter()
+                .map(|(pubkey, is_signer)| AccountMeta {
+                    pubkey: *pubkey,
+                    is_signer: *is_signer,
+                    is_writable: false,
+                }),
+        );
+        metas
+    }
+}
+
+fn keys_strategy() -> impl Strategy<Value = Vec<(Pubkey, bool)>> {
+    prop::collection::vec((0..POOL_SIZE, prop::bool::weighted(0.7)), 0..5).prop_map(|keys| {
+        keys.into_iter()
+            .map(|(index, is_signer)| (pool_key(index), is_signer))
+            .collect()
+    })
+}
+
+fn store_case_strategy() -> impl Strategy<Value = StoreCase> {
+    (
+        keys_strategy(),
+        // Often keep the stored keys, as an update would.
+        prop_oneof![2 => Just(None), 1 => keys_strategy().prop_map(Some)],
+        0..64usize,
+        0..128usize,
+        prop::bool::weighted(0.95),
+        prop::bool::weighted(0.8),
+        prop::bool::weighted(0.9),
+        // Either the new signers in order, each signing or not, or arbitrary
+        // accounts.
+        prop_oneof![
+            prop::collection::vec(prop::bool::weighted(0.9), 5).prop_map(Ok),
+            keys_strategy().prop_map(Err),
+        ],
+    )
+        .prop_map(
+            |(
+                stored_keys,
+                new_keys,
+                payload_len,
+                spare_space,
+                is_owned,
+                config_is_signer,
+                config_is_writable,
+                signer_accounts,
+            )| {
+                let new_keys = new_keys.unwrap_or_else(|| stored_keys.clone());
+                let signer_accounts = match signer_accounts {
+                    Ok(signed) => new_keys
+                        .iter()
+                        .filter(|(_, is_signer)| *is_signer)
+                        .zip(signed)
+                        .map(|((pubkey, _), signed)| (*pubkey, signed))
+                        .collect(),
+                    Err(accounts) => accounts,
+                };
+                StoreCase {
+                    stored_keys,
+                    new_keys,
+                    payload_len,
+                    spare_space,
+                    is_owned,
+                    config_is_signer,
+                    config_is_writable,
+                    signer_accounts,
+                }
+            },
+        )
+}
+
+/// The model of the `store` rules, returning the errors of the original
+/// builtin.
+fn model_store(case: &StoreCase) -> Result<(), ProgramError> {
+    let metas = case.account_metas();
+    // An account listed more than once signs if any of its entries does.
+    let signed = |pubkey: &Pubkey| {
+        metas
+            .iter()
+            .any(|meta| meta.pubkey == *pubkey && meta.is_signer)
+    };
+    let is_stored_signer = |pubkey: &Pubkey| case.stored_keys.contains(&(*pubkey, true));
+
+    if !case.is_owned {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let stored_signer_count = case
+        .stored_keys
+        .iter()
+        .filter(|(_, is_signer)| *is_signer)
+        .count();
+    if stored_signer_count == 0 && !signed(&config_key()) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let mut position = 0;
+    for (signer, _) in case.new_keys.iter().filter(|(_, is_signer)| *is_signer) {
+        position += 1;
+        if *signer == config_key() {
+            if !signed(signer) {
+                return Err(ProgramError::MissingRequiredSignature);
+            }
+            continue;
+        }
+        match metas.get(position) {
+            Some(meta) if meta.pubkey == *signer && signed(signer) => {}
+            _ => return Err(ProgramError::MissingRequiredSignature),
+        }
+        if !case.stored_keys.is_empty() && !is_stored_signer(signer) {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+    }
+
+    for (i, key) in case.new_keys.iter().enumerate() {
+        if case.new_keys[..i].contains(key) {
+            return Err(ProgramError::InvalidArgument);
+        }
+    }
+
+    if stored_signer_count > position {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if case.instruction_data().len() > case.account_space() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let is_writable = metas
+        .iter()
+        .any(|meta| meta.pubkey == config_key() && meta.is_writable);
+    if !is_writable {
+        return Err(ConfigError::ReadonlyDataModified.into());
+    }
+
+    Ok(())
+}
+
+fn run_store(mollusk: &Mollusk, case: &StoreCase) -> (ProgramResult, Vec<u8>, Vec<u8>) {
+    let mut account_data = bincode::serialize(&ConfigKeys {
+        keys: case.stored_keys.clone(),
+    })
+    .unwrap();
+    account_data.resize(case.account_space(), 0);
+    let owner = if case.is_owned {
+        solana_config_program::id()
+    } else {
+        Pubkey::new_unique()
+    };
+    let config_account = Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(account_data.len()),
+        data: account_data.clone(),
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let metas = case.account_metas();
+    let mut accounts = vec![(config_key(), config_account)];
+    for meta in &metas {
+        if !accounts.iter().any(|(pubkey, _)| *pubkey == meta.pubkey) {
+            accounts.push((meta.pubkey, Account::default()));
+        }
+    }
+
+    let data = case.instruction_data();
+    let instruction = Instruction::new_with_bytes(solana_config_program::id(), &data, metas);
+    let result = mollusk.process_instruction(&instruction, &accounts);
+
+    let mut expected_data = account_data;
+    if result.program_result.is_ok() {
+        expected_data[..data.len()].copy_from_slice(&data);
+    }
+    (
+        result.program_result,
+        result.resulting_accounts[0].1.data.clone(),
+        expected_data,
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn test_store_matches_model(case in store_case_strategy()) {
+        let mollusk = setup();
+        let (result, data, expected_data) = run_store(&mollusk, &case);
+
+        let expected = match model_store(&case) {
+            Ok(()) => ProgramResult::Success,
+            Err(err) => ProgramResult::Failure(err),
+        };
+        // With detailed errors, the program returns its own error codes, so
+        // only acceptance is compared.
+        if cfg!(feature = "detailed-errors") {
+            prop_assert_eq!(result.is_ok(), expected.is_ok(), "{:?}", result);
+        } else {
+            prop_assert_eq!(result, expected);
+        }
+        // The instruction data replaces the start of the account data, and
+        // the rest is left unchanged.
+        prop_assert_eq!(data, expected_data);
+    }
+}